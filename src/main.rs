@@ -3,8 +3,10 @@ use sysinfo::Disks;
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::time::SystemTime;
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::thread;
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -14,15 +16,36 @@ enum FileCategory {
     System,      // System files that should generally be kept
     Regular,     // Normal files
     Useless,     // Temp files, cache, logs, etc.
+    Duplicate,   // Byte-identical copy of another file in the tree
+    Suspicious,  // Real format doesn't match the declared extension
+    SimilarImage, // Perceptually near-duplicate of another image in the tree
+    Empty,       // Zero-byte regular file
+    EmptyFolder, // Directory with no children
+    BrokenSymlink, // Symlink whose target no longer resolves
     Unknown,     // Can't determine
 }
 
+impl FileCategory {
+    /// Whether this is a "degenerate" entry a cleanup scan surfaces on its own:
+    /// empty files, empty folders, and broken symlinks all carry no useful
+    /// content and are safe reclamation targets.
+    fn is_degenerate(self) -> bool {
+        matches!(
+            self,
+            FileCategory::Empty | FileCategory::EmptyFolder | FileCategory::BrokenSymlink
+        )
+    }
+}
+
 #[derive(Clone, Copy, PartialEq)]
 enum SortColumn {
     Name,
     Size,
     Category,
     Usefulness,
+    /// Cluster likely-duplicate entries together (by size, then name), so the
+    /// redundant copies in a tree line up next to each other.
+    DuplicateGroup,
 }
 
 #[derive(Clone, Copy, PartialEq)]
@@ -31,6 +54,240 @@ enum SortDirection {
     Descending,
 }
 
+/// Whether sizes are reported as apparent bytes (`metadata.len()`) or as the
+/// space actually allocated on disk, as ncdu offers.
+#[derive(Clone, Copy, PartialEq)]
+enum SizeMode {
+    Apparent,
+    DiskUsage,
+}
+
+impl SizeMode {
+    fn label(self) -> &'static str {
+        match self {
+            SizeMode::Apparent => "Apparent",
+            SizeMode::DiskUsage => "On disk",
+        }
+    }
+}
+
+/// Coarse file-type groups for the type-filter chips, following the extension
+/// buckets [`render_file_item`] uses for icon selection, plus `m4v` (video),
+/// `wma` (audio) and `sql` (code), which have no dedicated icon.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum FileTypeGroup {
+    Images,
+    Video,
+    Audio,
+    Documents,
+    Code,
+    Archives,
+    Executables,
+}
+
+impl FileTypeGroup {
+    /// Every group in toolbar order, with a display label.
+    const ALL: [(FileTypeGroup, &'static str); 7] = [
+        (FileTypeGroup::Images, "Images"),
+        (FileTypeGroup::Video, "Video"),
+        (FileTypeGroup::Audio, "Audio"),
+        (FileTypeGroup::Documents, "Documents"),
+        (FileTypeGroup::Code, "Code"),
+        (FileTypeGroup::Archives, "Archives"),
+        (FileTypeGroup::Executables, "Executables"),
+    ];
+}
+
+/// Map a lowercase extension to its [`FileTypeGroup`], if recognised.
+fn file_type_group(ext: &str) -> Option<FileTypeGroup> {
+    match ext {
+        "jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp" | "svg" | "ico" => Some(FileTypeGroup::Images),
+        "mp4" | "avi" | "mkv" | "mov" | "wmv" | "flv" | "webm" | "m4v" => Some(FileTypeGroup::Video),
+        "mp3" | "wav" | "flac" | "ogg" | "aac" | "m4a" | "wma" => Some(FileTypeGroup::Audio),
+        "pdf" | "doc" | "docx" | "xls" | "xlsx" | "ppt" | "pptx" | "txt" | "md" | "rtf" => {
+            Some(FileTypeGroup::Documents)
+        }
+        "rs" | "py" | "js" | "ts" | "java" | "c" | "cpp" | "h" | "cs" | "go" | "html" | "css"
+        | "json" | "xml" | "yaml" | "toml" | "sql" => Some(FileTypeGroup::Code),
+        "zip" | "rar" | "7z" | "tar" | "gz" | "bz2" => Some(FileTypeGroup::Archives),
+        "exe" | "msi" | "bat" | "cmd" | "ps1" | "sh" => Some(FileTypeGroup::Executables),
+        _ => None,
+    }
+}
+
+/// Whether an entry passes the active type-group chip filter.
+///
+/// An empty `active` set passes everything. Directories always pass so the
+/// tree stays navigable.
+fn passes_type_filter(name: &str, is_dir: bool, active: &HashSet<FileTypeGroup>) -> bool {
+    if active.is_empty() || is_dir {
+        return true;
+    }
+    let ext = Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    file_type_group(&ext).map(|g| active.contains(&g)).unwrap_or(false)
+}
+
+/// Which top-level view the dashboard is currently showing.
+#[derive(Clone, Copy, PartialEq)]
+enum AppView {
+    Files,
+    Trash,
+    Duplicates,
+    SimilarImages,
+    SimilarAudio,
+    Filesystems,
+}
+
+/// Sortable columns for the Filesystems panel.
+#[derive(Clone, Copy, PartialEq)]
+enum FsSortColumn {
+    Mount,
+    FsType,
+    Total,
+    Used,
+    Percent,
+}
+
+/// Photo extensions [`analyze_file`] recognises.
+const PHOTO_EXTENSIONS: &[&str] = &[
+    "jpg", "jpeg", "png", "gif", "bmp", "webp", "raw", "cr2", "nef", "arw",
+];
+
+/// Video containers [`analyze_file`] recognises and ffprobe can probe.
+const VIDEO_EXTENSIONS: &[&str] = &[
+    "mp4", "avi", "mkv", "mov", "wmv", "flv", "webm", "m4v",
+];
+
+/// Audio containers [`analyze_file`] recognises.
+const AUDIO_EXTENSIONS: &[&str] = &[
+    "mp3", "wav", "flac", "ogg", "aac", "m4a", "wma",
+];
+
+/// Whether `name` looks like a media file ffprobe could describe.
+fn is_media_file(name: &str) -> bool {
+    let ext = Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    VIDEO_EXTENSIONS.contains(&ext.as_str()) || AUDIO_EXTENSIONS.contains(&ext.as_str())
+}
+
+/// A message from a background folder-size worker back to the UI thread.
+///
+/// Every message carries the `generation` it was spawned in so the UI can
+/// discard stale results that arrive after the user has navigated away.
+enum SizeUpdate {
+    /// Incremental progress while a scan is still running.
+    Progress { generation: u64, files: u64, bytes: u64 },
+    /// Final size for a directory.
+    Complete { generation: u64, path: PathBuf, size: u64 },
+}
+
+/// Rich per-file media metadata decoded from an `ffprobe` probe.
+///
+/// All fields are best-effort: an older container may omit a framerate, an
+/// audio-only file has no `width`/`height`, and so on.
+#[derive(Clone, Default)]
+struct MediaInfo {
+    /// Container/format long name, e.g. `QuickTime / MOV`.
+    format: String,
+    /// Total duration in seconds.
+    duration: f64,
+    /// Codec long names, one per stream, in stream order.
+    codecs: Vec<String>,
+    width: Option<u32>,
+    height: Option<u32>,
+    /// Frames per second of the first video stream.
+    framerate: Option<f64>,
+    /// Channel count of the first audio stream.
+    channels: Option<u32>,
+    /// Sample rate (Hz) of the first audio stream.
+    sample_rate: Option<u32>,
+}
+
+/// A populated [`MediaInfo`] delivered from a background `ffprobe` worker,
+/// keyed by the file it describes so the UI can slot it into the right entry.
+struct MediaUpdate {
+    path: PathBuf,
+    /// Modification time the probe was taken at, so stale cache hits are avoided.
+    mtime: Option<SystemTime>,
+    info: MediaInfo,
+}
+
+/// Filesystem-level metadata for a mounted volume, beyond the size figures
+/// `sysinfo` already surfaces.
+#[derive(Clone, Default)]
+struct FsInfo {
+    /// Filesystem type, e.g. `ext4`, `ntfs`, `btrfs`, `apfs`.
+    fs_type: String,
+    /// Backing device, e.g. `/dev/sda1`.
+    device: String,
+    removable: bool,
+    read_only: bool,
+    network: bool,
+}
+
+/// A confirmed set of byte-identical files sharing one content hash.
+#[derive(Clone)]
+struct DuplicateGroup {
+    hash: u64,
+    paths: Vec<PathBuf>,
+    /// Size of a single member (all members share the same size).
+    size: u64,
+}
+
+impl DuplicateGroup {
+    /// Bytes that could be reclaimed by keeping a single copy.
+    fn reclaimable(&self) -> u64 {
+        reclaimable_bytes(self.size, self.paths.len())
+    }
+}
+
+/// A progress update emitted by a long-running scan so the UI can draw a
+/// determinate progress bar. `current_stage`/`max_stage` track which pass is
+/// running (1-based), and `entries_checked`/`entries_to_check` the work within
+/// the current pass. `tool_type` names the scan so one receiver can serve
+/// several tools.
+#[derive(Clone, Copy, Debug)]
+struct ProgressData {
+    current_stage: u32,
+    max_stage: u32,
+    entries_checked: usize,
+    entries_to_check: usize,
+    tool_type: &'static str,
+}
+
+impl ProgressData {
+    /// Stage names reported by [`scan_duplicates`], indexed by `current_stage - 1`
+    /// (stages are numbered from 1).
+    const DUPLICATE_STAGES: [&'static str; 3] = ["collecting", "pre-hash", "full-hash"];
+
+    /// Fraction of the current stage completed, in `0.0..=1.0`.
+    fn fraction(&self) -> f32 {
+        if self.entries_to_check == 0 {
+            0.0
+        } else {
+            (self.entries_checked as f32 / self.entries_to_check as f32).clamp(0.0, 1.0)
+        }
+    }
+}
+
+/// A single entry recovered from the OS trash/Recycle Bin.
+#[derive(Clone)]
+struct TrashItem {
+    /// Opaque handle used to restore or purge the entry via the `trash` crate.
+    id: trash::TrashItem,
+    name: String,
+    original_path: PathBuf,
+    deleted: Option<SystemTime>,
+    size: u64,
+}
+
 #[derive(Clone)]
 struct FileItem {
     path: PathBuf,
@@ -41,6 +298,13 @@ struct FileItem {
     usefulness: f32,        // 0-100 score
     modified: Option<SystemTime>,
     child_count: Option<usize>, // For directories: number of items inside
+    // Rich media metadata, filled in asynchronously once ffprobe returns.
+    media_info: Option<MediaInfo>,
+    // Matched a user exclude glob: kept for display but dimmed (unless hidden).
+    excluded: bool,
+    // Real format sniffed from the header when it contradicts the extension
+    // (drives the Suspicious category); None when the extension checks out.
+    real_type: Option<&'static str>,
 }
 
 struct DiskDashboard {
@@ -54,21 +318,40 @@ struct DiskDashboard {
     loading: bool,
     sort_column: SortColumn,
     sort_direction: SortDirection,
+    // Order names with a digit-aware natural comparator (file2 < file10) when
+    // set, falling back to raw lexical order otherwise.
+    natural_sort: bool,
     navigation_history: Vec<PathBuf>,
     history_index: usize,
     search_query: String,
     // Deletion confirmation
     pending_delete: Option<PathBuf>,
     delete_error: Option<String>,
+    // Path to retry after a failed delete (e.g. once a locking app is closed)
+    retry_delete: Option<PathBuf>,
     needs_refresh: bool,
     // Toast notifications
     toast_message: Option<(String, f32)>, // (message, time_remaining)
+    // Default delete behavior: move to the OS trash (recoverable) vs. permanent
+    // removal. Persisted in config.
+    delete_to_trash: bool,
+    // Trash entries created by the most recent trash operation, offered for
+    // Undo while the toast is still on screen. Cleared when the toast expires.
+    undo_trash: Option<Vec<trash::TrashItem>>,
     // Folder size cache for efficient recursive size calculation
     folder_size_cache: HashMap<PathBuf, u64>,
     // Async folder size calculation
-    size_sender: Sender<(PathBuf, u64)>,
-    size_receiver: Receiver<(PathBuf, u64)>,
+    size_sender: Sender<SizeUpdate>,
+    size_receiver: Receiver<SizeUpdate>,
     pending_size_calculations: HashSet<PathBuf>,
+    // Scan generation + cancellation so stale results are dropped and workers stop
+    scan_generation: u64,
+    size_cancel: Arc<AtomicBool>,
+    // Size-scan worker pool: configurable concurrency + live worker count
+    size_worker_threads: usize,
+    size_workers_active: Arc<AtomicUsize>,
+    // Live progress for the in-flight scan: (files walked, bytes accumulated)
+    scan_progress: Option<(u64, u64)>,
     // Multi-file selection
     selected_items: HashSet<PathBuf>,
     last_selected_index: Option<usize>,
@@ -77,11 +360,111 @@ struct DiskDashboard {
     is_selecting: bool, // True when mouse held for selection
     // Track loaded path to avoid reloading every frame
     last_loaded_path: Option<PathBuf>,
+    // Active top-level view (file browser vs. trash)
+    view: AppView,
+    // Sort state for the Filesystems panel
+    fs_sort_column: FsSortColumn,
+    fs_sort_desc: bool,
+    // Cached listing of items currently in the OS trash
+    trash_items: Vec<TrashItem>,
+    selected_trash: Option<usize>,
+    // Extension / size filter chain applied in apply_filter_and_sort
+    allowed_extensions: HashSet<String>,
+    excluded_extensions: HashSet<String>,
+    min_size_filter: u64,
+    // Path substrings whose matches are skipped entirely during scanning
+    excluded_paths: Vec<String>,
+    excluded_paths_input: String,
+    // ncdu-style glob exclude patterns (e.g. */node_modules, *.iso, $*) that are
+    // neither descended into nor counted toward folder sizes. Persisted in config.
+    exclude_globs: Vec<String>,
+    exclude_globs_input: String,
+    // `.gitignore`/`.ignore` rules found in the browsed directory; matching
+    // immediate children are dropped from the listing. Reloaded on each scan.
+    ignore_set: IgnoreSet,
+    show_exclude_editor: bool,
+    // When set, excluded items are dropped from filtered_items entirely;
+    // otherwise they are kept but dimmed.
+    hide_excluded: bool,
+    show_filter_settings: bool,
+    // Comma-separated edit buffers backing the settings panel
+    allowed_ext_input: String,
+    excluded_ext_input: String,
+    min_size_mb_input: String,
+    // Duplicate-file finder
+    duplicate_groups: Vec<DuplicateGroup>,
+    dup_sender: Sender<DuplicateGroup>,
+    dup_receiver: Receiver<DuplicateGroup>,
+    dup_scanning: bool,
+    dup_cancel: Arc<AtomicBool>,
+    // Staged progress reported by the duplicate scan (latest update, or None
+    // before the first tick)
+    dup_progress_sender: Sender<ProgressData>,
+    dup_progress_receiver: Receiver<ProgressData>,
+    dup_progress: Option<ProgressData>,
+    // Completion sentinel: the worker sends once when the scan finishes so the
+    // UI can clear `dup_scanning` and stop busy-repainting.
+    dup_done_sender: Sender<()>,
+    dup_done_receiver: Receiver<()>,
+    // Perceptual near-duplicate image finder
+    similar_groups: Vec<Vec<PathBuf>>,
+    sim_sender: Sender<Vec<PathBuf>>,
+    sim_receiver: Receiver<Vec<PathBuf>>,
+    // Completion sentinel shared by the image and audio scans.
+    sim_done_sender: Sender<()>,
+    sim_done_receiver: Receiver<()>,
+    sim_scanning: bool,
+    sim_threshold: u32,
+    // Acoustic-fingerprint near-duplicate audio finder (reuses similar_groups)
+    sim_audio_threshold: f32,
+    // Edit buffers for the rule-based selection menu
+    select_usefulness_below: f32,
+    select_older_than_days: u32,
+    // User-pinned bookmarks (persisted) and most-recently-visited directories
+    bookmarks: Vec<PathBuf>,
+    recent_dirs: Vec<PathBuf>,
+    // Active type-group chips and a "useless only" category toggle
+    active_type_filters: HashSet<FileTypeGroup>,
+    only_useless: bool,
+    /// Cleanup scan mode: show only degenerate entries (empty files/folders,
+    /// broken symlinks) so the crate acts as a dead-weight reaper.
+    only_degenerate: bool,
+    // Apparent vs on-disk (allocated) size reporting
+    size_mode: SizeMode,
+    // Filesystem watcher on the current directory (auto-refresh)
+    watcher: Option<notify::RecommendedWatcher>,
+    watch_sender: Sender<notify::Event>,
+    watch_receiver: Receiver<notify::Event>,
+    watched_path: Option<PathBuf>,
+    // Debounce accumulator: Some(seconds_remaining) once events arrive
+    watch_debounce: Option<f32>,
+    // Paths reported by the watcher awaiting the next debounced merge
+    watch_changed: Vec<PathBuf>,
+    // Async media-metadata extraction via ffprobe
+    media_sender: Sender<MediaUpdate>,
+    media_receiver: Receiver<MediaUpdate>,
+    // Results cache keyed by (path, mtime) so rescans reuse prior probes
+    media_cache: HashMap<(PathBuf, Option<SystemTime>), MediaInfo>,
+    // Paths with a probe currently in flight (dedup against repeated draws)
+    media_pending: HashSet<PathBuf>,
+    // Feature-detected once: whether ffprobe is on PATH. None until probed.
+    ffprobe_available: Option<bool>,
+    // Thumbnail previews for photos/videos in the file list, and their cache.
+    show_thumbnails: bool,
+    thumbnails: ThumbnailCache,
 }
 
 impl Default for DiskDashboard {
     fn default() -> Self {
         let (sender, receiver) = channel();
+        let (dup_sender, dup_receiver) = channel();
+        let (dup_progress_sender, dup_progress_receiver) = channel();
+        let (dup_done_sender, dup_done_receiver) = channel();
+        let (sim_sender, sim_receiver) = channel();
+        let (sim_done_sender, sim_done_receiver) = channel();
+        let (watch_sender, watch_receiver) = channel();
+        let (media_sender, media_receiver) = channel();
+        let exclude_globs = load_exclude_globs();
         Self {
             disks: Disks::new_with_refreshed_list(),
             refresh_interval: 1.0,
@@ -93,23 +476,90 @@ impl Default for DiskDashboard {
             loading: false,
             sort_column: SortColumn::Size,
             sort_direction: SortDirection::Descending,
+            natural_sort: true,
             navigation_history: Vec::new(),
             history_index: 0,
             search_query: String::new(),
             pending_delete: None,
             delete_error: None,
+            retry_delete: None,
             needs_refresh: false,
             toast_message: None,
+            delete_to_trash: load_delete_to_trash(),
+            undo_trash: None,
             folder_size_cache: HashMap::new(),
             size_sender: sender,
             size_receiver: receiver,
             pending_size_calculations: HashSet::new(),
+            scan_generation: 0,
+            size_cancel: Arc::new(AtomicBool::new(false)),
+            size_worker_threads: default_size_worker_threads(),
+            size_workers_active: Arc::new(AtomicUsize::new(0)),
+            scan_progress: None,
             selected_items: HashSet::new(),
             last_selected_index: None,
             selection_anchor: None,
             selection_end: None,
             is_selecting: false,
             last_loaded_path: None,
+            view: AppView::Files,
+            fs_sort_column: FsSortColumn::Percent,
+            fs_sort_desc: true,
+            trash_items: Vec::new(),
+            selected_trash: None,
+            allowed_extensions: HashSet::new(),
+            excluded_extensions: HashSet::new(),
+            min_size_filter: 0,
+            excluded_paths: Vec::new(),
+            excluded_paths_input: String::new(),
+            exclude_globs_input: exclude_globs.join(", "),
+            exclude_globs,
+            ignore_set: IgnoreSet::default(),
+            show_exclude_editor: false,
+            hide_excluded: false,
+            show_filter_settings: false,
+            allowed_ext_input: String::new(),
+            excluded_ext_input: String::new(),
+            min_size_mb_input: String::new(),
+            duplicate_groups: Vec::new(),
+            dup_sender,
+            dup_receiver,
+            dup_scanning: false,
+            dup_cancel: Arc::new(AtomicBool::new(false)),
+            dup_progress_sender,
+            dup_progress_receiver,
+            dup_progress: None,
+            dup_done_sender,
+            dup_done_receiver,
+            similar_groups: Vec::new(),
+            sim_sender,
+            sim_receiver,
+            sim_done_sender,
+            sim_done_receiver,
+            sim_scanning: false,
+            sim_threshold: 10,
+            sim_audio_threshold: 0.15,
+            select_usefulness_below: 20.0,
+            select_older_than_days: 365,
+            bookmarks: load_bookmarks(),
+            recent_dirs: Vec::new(),
+            active_type_filters: HashSet::new(),
+            only_useless: false,
+            only_degenerate: false,
+            size_mode: SizeMode::Apparent,
+            watcher: None,
+            watch_sender,
+            watch_receiver,
+            watched_path: None,
+            watch_debounce: None,
+            watch_changed: Vec::new(),
+            media_sender,
+            media_receiver,
+            media_cache: HashMap::new(),
+            media_pending: HashSet::new(),
+            ffprobe_available: None,
+            show_thumbnails: true,
+            thumbnails: ThumbnailCache::new(THUMBNAIL_CACHE_CAPACITY),
         }
     }
 }
@@ -149,6 +599,8 @@ impl eframe::App for DiskDashboard {
             *time_left -= dt;
             if *time_left <= 0.0 {
                 self.toast_message = None;
+                // The Undo affordance lives only as long as its toast.
+                self.undo_trash = None;
             } else {
                 ctx.request_repaint(); // Keep animating
             }
@@ -156,10 +608,26 @@ impl eframe::App for DiskDashboard {
 
         // Check for completed async folder size calculations
         let mut sizes_updated = false;
-        while let Ok((path, size)) = self.size_receiver.try_recv() {
-            self.folder_size_cache.insert(path.clone(), size);
-            self.pending_size_calculations.remove(&path);
-            sizes_updated = true;
+        while let Ok(update) = self.size_receiver.try_recv() {
+            match update {
+                // Drop stale results from a previous scan generation.
+                SizeUpdate::Progress { generation, files, bytes } => {
+                    if generation == self.scan_generation {
+                        self.scan_progress = Some((files, bytes));
+                    }
+                }
+                SizeUpdate::Complete { generation, path, size } => {
+                    if generation != self.scan_generation {
+                        continue;
+                    }
+                    self.folder_size_cache.insert(path.clone(), size);
+                    self.pending_size_calculations.remove(&path);
+                    sizes_updated = true;
+                }
+            }
+        }
+        if self.pending_size_calculations.is_empty() {
+            self.scan_progress = None;
         }
         // Update file items with new sizes
         if sizes_updated {
@@ -178,6 +646,92 @@ impl eframe::App for DiskDashboard {
             ctx.request_repaint();
         }
 
+        // Drain completed ffprobe results and attach them to their entries.
+        let mut media_updated = false;
+        while let Ok(update) = self.media_receiver.try_recv() {
+            self.media_pending.remove(&update.path);
+            self.media_cache
+                .insert((update.path.clone(), update.mtime), update.info.clone());
+            for item in &mut self.file_items {
+                if item.path == update.path {
+                    // Refine the deletability score now that resolution/duration are known.
+                    item.usefulness = refine_media_usefulness(item.usefulness, &update.info);
+                    item.media_info = Some(update.info.clone());
+                }
+            }
+            media_updated = true;
+        }
+        if media_updated {
+            ctx.request_repaint();
+        }
+
+        // Drain incoming duplicate groups from the background finder.
+        while let Ok(group) = self.dup_receiver.try_recv() {
+            self.duplicate_groups.push(group);
+        }
+        // Keep only the most recent staged-progress tick for the UI.
+        while let Ok(update) = self.dup_progress_receiver.try_recv() {
+            self.dup_progress = Some(update);
+        }
+        // Clear the scanning flag once the worker signals completion.
+        if self.dup_done_receiver.try_recv().is_ok() {
+            self.dup_scanning = false;
+            // Final ordering after the last groups have been drained.
+            self.duplicate_groups.sort_by(|a, b| b.reclaimable().cmp(&a.reclaimable()));
+            ctx.request_repaint();
+        }
+        if self.dup_scanning {
+            // Largest reclaimable groups first for an actionable ordering.
+            self.duplicate_groups.sort_by(|a, b| b.reclaimable().cmp(&a.reclaimable()));
+            ctx.request_repaint();
+        }
+
+        // Coalesce filesystem-watch events and refresh the view once they settle.
+        let mut new_paths: Vec<PathBuf> = Vec::new();
+        while let Ok(event) = self.watch_receiver.try_recv() {
+            new_paths.extend(event.paths);
+        }
+        if !new_paths.is_empty() {
+            // Invalidate cached sizes for each newly-reported path's own chain.
+            for path in &new_paths {
+                let mut ancestor = Some(path.as_path());
+                while let Some(dir) = ancestor {
+                    self.folder_size_cache.remove(dir);
+                    self.pending_size_calculations.remove(dir);
+                    ancestor = dir.parent();
+                }
+            }
+            self.watch_changed.extend(new_paths);
+            self.watch_debounce = Some(0.3); // restart the debounce window
+        }
+        if let Some(remaining) = self.watch_debounce {
+            let remaining = remaining - dt;
+            if remaining <= 0.0 {
+                self.watch_debounce = None;
+                // Merge only the touched entries so the user's selection survives.
+                let changed: Vec<PathBuf> = self.watch_changed.drain(..).collect();
+                if self.current_path.is_some() {
+                    self.refresh_changed_entries(&changed);
+                    self.toast_message = Some(("üîÑ Changed on disk ‚Äî view updated".to_string(), 2.0));
+                }
+            } else {
+                self.watch_debounce = Some(remaining);
+            }
+            ctx.request_repaint();
+        }
+
+        // Drain near-duplicate image clusters.
+        while let Ok(cluster) = self.sim_receiver.try_recv() {
+            self.similar_groups.push(cluster);
+        }
+        // Clear the scanning flag once the image/audio worker signals completion.
+        if self.sim_done_receiver.try_recv().is_ok() {
+            self.sim_scanning = false;
+        }
+        if self.sim_scanning {
+            ctx.request_repaint();
+        }
+
         // Handle keyboard shortcuts and scroll selection
         ctx.input(|i| {
             // Mouse forward/backward buttons
@@ -367,36 +921,71 @@ impl eframe::App for DiskDashboard {
 
                                 ui.add_space(20.0);
 
-                                if ui.add(egui::Button::new("Delete")
+                                let _ = is_dir;
+                                // Primary button follows the configured default;
+                                // the Recycle-Bin path captures an Undo affordance
+                                // and keeps the locking-process retry hint.
+                                let primary = if self.delete_to_trash { "‚ôªÔ∏è Recycle Bin" } else { "Delete Permanently" };
+                                if ui.add(egui::Button::new(primary)
                                     .fill(egui::Color32::from_rgb(180, 50, 50))
-                                    .min_size(egui::Vec2::new(80.0, 30.0)))
+                                    .min_size(egui::Vec2::new(110.0, 30.0)))
                                     .clicked()
                                 {
-                                    // Perform deletion
-                                    let result = if is_dir {
-                                        fs::remove_dir_all(&path_to_delete)
-                                    } else {
-                                        fs::remove_file(&path_to_delete)
-                                    };
-
-                                    match result {
-                                        Ok(_) => {
+                                    if self.delete_to_trash {
+                                        let (_, new_items, errors) = trash_paths(std::slice::from_ref(&path_to_delete));
+                                        if let Some(e) = errors.into_iter().next() {
+                                            self.delete_error = Some(describe_delete_error(&path_to_delete, &e));
+                                            self.retry_delete = Some(path_to_delete.clone());
+                                        } else {
                                             self.delete_error = None;
                                             self.needs_refresh = true;
-                                            // Invalidate size cache for parent and ancestors
-                                            let mut ancestor = path_to_delete.parent();
-                                            while let Some(parent) = ancestor {
-                                                self.folder_size_cache.remove(parent);
-                                                self.pending_size_calculations.remove(parent);
-                                                ancestor = parent.parent();
-                                            }
+                                            self.undo_trash = if new_items.is_empty() { None } else { Some(new_items) };
+                                            self.toast_message = Some(("‚ôªÔ∏è Moved to Recycle Bin ‚Äî Undo".to_string(), 5.0));
+                                            self.invalidate_size_ancestors(&path_to_delete);
                                         }
-                                        Err(e) => {
-                                            self.delete_error = Some(format!("Failed to delete: {}", e));
+                                    } else {
+                                        match delete_permanently(&path_to_delete) {
+                                            Ok(_) => {
+                                                self.delete_error = None;
+                                                self.needs_refresh = true;
+                                                self.undo_trash = None;
+                                                self.toast_message = Some(("Deleted permanently".to_string(), 2.0));
+                                                self.invalidate_size_ancestors(&path_to_delete);
+                                            }
+                                            Err(e) => {
+                                                self.delete_error = Some(describe_delete_error(&path_to_delete, &e.to_string()));
+                                                self.retry_delete = Some(path_to_delete.clone());
+                                            }
                                         }
                                     }
                                     self.pending_delete = None;
                                 }
+
+                                // Secondary permanent delete, shown only when the
+                                // default is trashing so it stays explicit.
+                                if self.delete_to_trash {
+                                    ui.add_space(10.0);
+                                    if ui.add(egui::Button::new("‚ö†Ô∏è Permanently")
+                                        .fill(egui::Color32::from_rgb(90, 30, 30))
+                                        .min_size(egui::Vec2::new(90.0, 30.0)))
+                                        .clicked()
+                                    {
+                                        match delete_permanently(&path_to_delete) {
+                                            Ok(_) => {
+                                                self.delete_error = None;
+                                                self.needs_refresh = true;
+                                                self.undo_trash = None;
+                                                self.toast_message = Some(("Deleted permanently".to_string(), 2.0));
+                                                self.invalidate_size_ancestors(&path_to_delete);
+                                            }
+                                            Err(e) => {
+                                                self.delete_error = Some(describe_delete_error(&path_to_delete, &e.to_string()));
+                                                self.retry_delete = Some(path_to_delete.clone());
+                                            }
+                                        }
+                                        self.pending_delete = None;
+                                    }
+                                }
                             });
                         }
                         ui.add_space(10.0);
@@ -419,9 +1008,31 @@ impl eframe::App for DiskDashboard {
                         ui.add_space(10.0);
                         ui.label(&error);
                         ui.add_space(15.0);
-                        if ui.button("OK").clicked() {
-                            self.delete_error = None;
-                        }
+                        ui.horizontal(|ui| {
+                            if ui.button("OK").clicked() {
+                                self.delete_error = None;
+                                self.retry_delete = None;
+                            }
+                            // Retry once the locking process has been closed.
+                            if let Some(path) = self.retry_delete.clone() {
+                                if ui.add(egui::Button::new("üîÑ Retry")
+                                    .fill(egui::Color32::from_rgb(40, 60, 90)))
+                                    .clicked()
+                                {
+                                    match trash::delete(&path) {
+                                        Ok(_) => {
+                                            self.delete_error = None;
+                                            self.retry_delete = None;
+                                            self.needs_refresh = true;
+                                            self.toast_message = Some(("‚ôªÔ∏è Moved to Recycle Bin".to_string(), 2.0));
+                                        }
+                                        Err(e) => {
+                                            self.delete_error = Some(describe_delete_error(&path, &e.to_string()));
+                                        }
+                                    }
+                                }
+                            }
+                        });
                         ui.add_space(10.0);
                     });
                 });
@@ -445,7 +1056,41 @@ impl eframe::App for DiskDashboard {
                                 .color(egui::Color32::from_rgb(255, 0, 255)));
 
                             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                if self.current_path.is_some() {
+                                // Recycle Bin toggle, parallel to HOME.
+                                let trash_selected = self.view == AppView::Trash;
+                                if ui.add(egui::Button::new("‚ôªÔ∏è RECYCLE BIN")
+                                    .fill(if trash_selected {
+                                        egui::Color32::from_rgb(50, 30, 70)
+                                    } else {
+                                        egui::Color32::from_rgb(30, 20, 50)
+                                    })
+                                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 0, 255))))
+                                    .clicked() {
+                                    if trash_selected {
+                                        self.view = AppView::Files;
+                                    } else {
+                                        self.view = AppView::Trash;
+                                        self.refresh_trash();
+                                    }
+                                }
+                                // Whole-machine filesystems overview toggle.
+                                let fs_selected = self.view == AppView::Filesystems;
+                                if ui.add(egui::Button::new("üñß FILESYSTEMS")
+                                    .fill(if fs_selected {
+                                        egui::Color32::from_rgb(50, 30, 70)
+                                    } else {
+                                        egui::Color32::from_rgb(30, 20, 50)
+                                    })
+                                    .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(0, 255, 255))))
+                                    .clicked() {
+                                    if fs_selected {
+                                        self.view = AppView::Files;
+                                    } else {
+                                        self.view = AppView::Filesystems;
+                                        self.disks.refresh();
+                                    }
+                                }
+                                if self.view == AppView::Files && self.current_path.is_some() {
                                     if ui.add(egui::Button::new("‚åÇ HOME")
                                         .fill(egui::Color32::from_rgb(30, 20, 50))
                                         .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(0, 255, 255))))
@@ -488,12 +1133,63 @@ impl eframe::App for DiskDashboard {
                 
                 disk_data.sort_by(|a, b| a.0.to_string_lossy().cmp(&b.0.to_string_lossy()));
 
+                // Filesystem-level metadata (type/device/flags) keyed by mount point.
+                let fs_infos: HashMap<PathBuf, FsInfo> = self.disks.list().iter()
+                    .map(|d| (d.mount_point().to_path_buf(), fs_info_from_disk(d)))
+                    .collect();
+
                 egui::ScrollArea::vertical()
                     .auto_shrink([false; 2])
                     .show(ui, |ui| {
                     // Set minimum width to fill panel
                     ui.set_min_width(ui.available_width());
 
+                    // Quick access: standard folders, bookmarks and recents.
+                    egui::CollapsingHeader::new("Quick access")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            for (label, dir) in quick_location_dirs() {
+                                if ui.button(label).clicked() {
+                                    self.navigate_to(dir);
+                                }
+                            }
+                            if !self.bookmarks.is_empty() {
+                                ui.separator();
+                                ui.label(egui::RichText::new("Bookmarks")
+                                    .size(11.0)
+                                    .color(egui::Color32::from_rgb(120, 100, 160)));
+                                for bm in self.bookmarks.clone() {
+                                    let name = bm.file_name()
+                                        .map(|n| n.to_string_lossy().to_string())
+                                        .unwrap_or_else(|| bm.to_string_lossy().to_string());
+                                    ui.horizontal(|ui| {
+                                        if ui.button(format!("‚≠ê {}", name)).clicked() {
+                                            self.navigate_to(bm.clone());
+                                        }
+                                        if ui.small_button("‚úñ").clicked() {
+                                            self.bookmarks.retain(|p| p != &bm);
+                                            save_bookmarks(&self.bookmarks);
+                                        }
+                                    });
+                                }
+                            }
+                            if !self.recent_dirs.is_empty() {
+                                ui.separator();
+                                ui.label(egui::RichText::new("Recent")
+                                    .size(11.0)
+                                    .color(egui::Color32::from_rgb(120, 100, 160)));
+                                for dir in self.recent_dirs.clone() {
+                                    let name = dir.file_name()
+                                        .map(|n| n.to_string_lossy().to_string())
+                                        .unwrap_or_else(|| dir.to_string_lossy().to_string());
+                                    if ui.button(format!("üïì {}", name)).clicked() {
+                                        self.navigate_to(dir.clone());
+                                    }
+                                }
+                            }
+                        });
+                    ui.separator();
+
                     for (mount_point, disk_name, total, available, percent) in &disk_data {
                         // Skip empty/invalid entries
                         if *total == 0 {
@@ -511,13 +1207,7 @@ impl eframe::App for DiskDashboard {
                             .unwrap_or(false);
                         
                         // Cyberpunk disk card with neon colors
-                        let usage_color = if percent_clone > 90.0 {
-                            egui::Color32::from_rgb(255, 51, 102)   // Neon red
-                        } else if percent_clone > 75.0 {
-                            egui::Color32::from_rgb(255, 136, 0)    // Neon orange
-                        } else {
-                            egui::Color32::from_rgb(0, 255, 136)    // Neon green
-                        };
+                        let usage_color = usage_color(percent_clone);
 
                         // Create an interactive area to detect hover BEFORE drawing
                         let card_id = ui.make_persistent_id(format!("disk_card_{}", mount_point.to_string_lossy()));
@@ -591,9 +1281,30 @@ impl eframe::App for DiskDashboard {
                                         total_clone as f64 / 1_000_000_000.0))
                                         .size(11.0)
                                         .color(egui::Color32::from_gray(160)));
+
+                                    // Filesystem type, backing device and volume flags.
+                                    if let Some(info) = fs_infos.get(mount_point) {
+                                        ui.add_space(2.0);
+                                        let mut meta = info.fs_type.to_uppercase();
+                                        if !info.device.is_empty() {
+                                            meta = format!("{} ‚Ä¢ {}", meta, info.device);
+                                        }
+                                        ui.label(egui::RichText::new(meta)
+                                            .size(10.0)
+                                            .color(egui::Color32::from_rgb(120, 160, 200)));
+                                        let mut flags = Vec::new();
+                                        if info.read_only { flags.push("read-only"); }
+                                        if info.removable { flags.push("removable"); }
+                                        if info.network { flags.push("network"); }
+                                        if !flags.is_empty() {
+                                            ui.label(egui::RichText::new(flags.join(" ‚Ä¢ "))
+                                                .size(10.0)
+                                                .color(egui::Color32::from_rgb(255, 136, 0)));
+                                        }
+                                    }
                                 });
                             });
-                        
+
                         // Handle click on the interactive area
                         if interact_response.clicked() {
                             self.navigate_to(mount_clone.clone());
@@ -683,9 +1394,34 @@ impl eframe::App for DiskDashboard {
                 }); // Close ScrollArea
             }); // Close SidePanel
 
+        // Footer: apparent vs on-disk size mode (ncdu-style).
+        egui::TopBottomPanel::bottom("footer").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(egui::RichText::new("Size:")
+                    .size(11.0)
+                    .color(egui::Color32::from_rgb(120, 100, 160)));
+                let mut changed = false;
+                changed |= ui.selectable_value(&mut self.size_mode, SizeMode::Apparent, "Apparent").changed();
+                changed |= ui.selectable_value(&mut self.size_mode, SizeMode::DiskUsage, "On disk").changed();
+                if changed {
+                    self.apply_filter_and_sort();
+                }
+            });
+        });
+
         let current_path_clone = self.current_path.clone();
         egui::CentralPanel::default().show(ctx, |ui| {
-            if let Some(ref path) = current_path_clone {
+            if self.view == AppView::Trash {
+                self.render_trash_view(ui);
+            } else if self.view == AppView::Duplicates {
+                self.render_duplicates_view(ui);
+            } else if self.view == AppView::SimilarImages {
+                self.render_similar_images_view(ui);
+            } else if self.view == AppView::SimilarAudio {
+                self.render_similar_audio_view(ui);
+            } else if self.view == AppView::Filesystems {
+                self.render_filesystems_view(ui);
+            } else if let Some(ref path) = current_path_clone {
                 let path_clone = path.clone();
                 self.render_file_browser(ui, &path_clone);
             } else {
@@ -694,8 +1430,12 @@ impl eframe::App for DiskDashboard {
         });
 
         // Render cyberpunk toast notification overlay
-        if let Some((ref message, time_left)) = self.toast_message {
+        if let Some((message, time_left)) = self.toast_message.clone() {
             let opacity = (time_left.min(0.3) / 0.3).min(1.0); // Fade out in last 0.3s
+            // An Undo button rides alongside the toast while there are trash
+            // entries from the most recent trash operation to restore.
+            let can_undo = self.undo_trash.is_some();
+            let mut undo_clicked = false;
             egui::Area::new(egui::Id::new("toast_notification"))
                 .anchor(egui::Align2::CENTER_BOTTOM, [0.0, -50.0])
                 .show(ctx, |ui| {
@@ -705,11 +1445,36 @@ impl eframe::App for DiskDashboard {
                         .rounding(8.0)
                         .inner_margin(egui::Margin::symmetric(20.0, 12.0))
                         .show(ui, |ui| {
-                            ui.label(egui::RichText::new(message)
-                                .size(14.0)
-                                .color(egui::Color32::from_rgba_unmultiplied(0, 255, 255, (255.0 * opacity) as u8)));
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(&message)
+                                    .size(14.0)
+                                    .color(egui::Color32::from_rgba_unmultiplied(0, 255, 255, (255.0 * opacity) as u8)));
+                                if can_undo {
+                                    ui.add_space(8.0);
+                                    if ui.add(egui::Button::new(egui::RichText::new("‚Ü∂ Undo").size(13.0))
+                                        .fill(egui::Color32::from_rgb(40, 60, 90)))
+                                        .clicked()
+                                    {
+                                        undo_clicked = true;
+                                    }
+                                }
+                            });
                         });
                 });
+            if undo_clicked {
+                if let Some(items) = self.undo_trash.take() {
+                    let n = items.len();
+                    match trash::os_limited::restore_all(items) {
+                        Ok(_) => {
+                            self.toast_message = Some((format!("‚ôªÔ∏è Restored {} items", n), 2.0));
+                            self.needs_refresh = true;
+                        }
+                        Err(e) => {
+                            self.toast_message = Some((format!("‚ö†Ô∏è Undo failed: {}", e), 3.0));
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -729,240 +1494,887 @@ impl DiskDashboard {
     }
 
 
-    fn load_directory(&mut self, path: &Path) {
-        self.loading = true;
-        self.file_items.clear();
+    /// Whole-machine overview: every mounted volume with its type, device and
+    /// usage, rendered as a sortable table. Clicking a row browses that mount.
+    fn render_filesystems_view(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading(egui::RichText::new("üñß Filesystems")
+                .size(22.0)
+                .color(egui::Color32::from_rgb(0, 255, 255)));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button("üîÑ Refresh").clicked() {
+                    self.disks.refresh();
+                }
+            });
+        });
 
-        if let Ok(entries) = fs::read_dir(path) {
-            for entry in entries.flatten() {
-                let entry_path = entry.path();
-                let metadata = entry.metadata().ok();
+        // Snapshot each volume's type/device/usage for the table.
+        let mut rows: Vec<(PathBuf, String, String, u64, u64, u64, f64)> = self.disks.list().iter()
+            .filter(|d| d.total_space() > 0)
+            .map(|d| {
+                let info = fs_info_from_disk(d);
+                let total = d.total_space();
+                let available = d.available_space();
+                let used = total.saturating_sub(available);
+                let percent = if total > 0 { (used as f64 / total as f64) * 100.0 } else { 0.0 };
+                (d.mount_point().to_path_buf(), info.fs_type, info.device, total, used, available, percent)
+            })
+            .collect();
+
+        let dir = if self.fs_sort_desc { std::cmp::Ordering::Greater } else { std::cmp::Ordering::Less };
+        rows.sort_by(|a, b| {
+            let ord = match self.fs_sort_column {
+                FsSortColumn::Mount => a.0.to_string_lossy().cmp(&b.0.to_string_lossy()),
+                FsSortColumn::FsType => a.1.to_lowercase().cmp(&b.1.to_lowercase()),
+                FsSortColumn::Total => a.3.cmp(&b.3),
+                FsSortColumn::Used => a.4.cmp(&b.4),
+                FsSortColumn::Percent => a.6.partial_cmp(&b.6).unwrap_or(std::cmp::Ordering::Equal),
+            };
+            if dir == std::cmp::Ordering::Greater { ord.reverse() } else { ord }
+        });
 
-                let is_dir = metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false);
-                let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+        ui.label(egui::RichText::new(format!("{} mounted volumes", rows.len()))
+            .color(egui::Color32::from_rgb(120, 100, 160)));
+        ui.separator();
 
-                let name = entry_path.file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("Unknown")
-                    .to_string();
+        // Collect clicks into locals so the table can borrow `rows` immutably;
+        // the resulting sort/navigation mutations are applied after the grid.
+        let (cur_col, cur_desc) = (self.fs_sort_column, self.fs_sort_desc);
+        let mut header_click: Option<FsSortColumn> = None;
+        let mut row_click: Option<PathBuf> = None;
 
-                // Calculate size: for files use metadata, for dirs calculate recursive size
-                let (size, child_count) = if is_dir {
-                    let count = fs::read_dir(&entry_path).ok().map(|rd| rd.count());
-                    // Use cached recursive size or calculate it
-                    let dir_size = self.get_folder_size_recursive(&entry_path);
-                    (dir_size, count)
-                } else {
-                    (metadata.as_ref().map(|m| m.len()).unwrap_or(0), None)
+        egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                // Clickable column header that flips direction when already active.
+                let header = |ui: &mut egui::Ui, label: &str, col: FsSortColumn, click: &mut Option<FsSortColumn>| {
+                    let active = cur_col == col;
+                    let arrow = if active { if cur_desc { " ‚ñº" } else { " ‚ñ≤" } } else { "" };
+                    if ui.add(egui::Label::new(egui::RichText::new(format!("{}{}", label, arrow))
+                        .strong()
+                        .color(if active { egui::Color32::from_rgb(0, 255, 255) } else { egui::Color32::from_gray(180) }))
+                        .sense(egui::Sense::click())).clicked() {
+                        *click = Some(col);
+                    }
                 };
 
-                let (category, usefulness) = self.analyze_file(&entry_path, &name, is_dir, size);
+                egui::Grid::new("filesystems_table")
+                    .striped(true)
+                    .num_columns(6)
+                    .spacing([16.0, 6.0])
+                    .show(ui, |ui| {
+                        header(ui, "Mount", FsSortColumn::Mount, &mut header_click);
+                        header(ui, "Type", FsSortColumn::FsType, &mut header_click);
+                        ui.label(egui::RichText::new("Device").strong().color(egui::Color32::from_gray(180)));
+                        header(ui, "Total", FsSortColumn::Total, &mut header_click);
+                        header(ui, "Used", FsSortColumn::Used, &mut header_click);
+                        header(ui, "Full", FsSortColumn::Percent, &mut header_click);
+                        ui.end_row();
+
+                        for (mount, fs_type, device, total, used, _available, percent) in &rows {
+                            let color = usage_color(*percent);
+                            let mount_str = mount.to_string_lossy().to_string();
+                            if ui.add(egui::Label::new(egui::RichText::new(&mount_str)
+                                .color(egui::Color32::from_rgb(200, 180, 255)))
+                                .sense(egui::Sense::click())).clicked() {
+                                row_click = Some(mount.clone());
+                            }
+                            ui.label(if fs_type.is_empty() { "‚Äî".to_string() } else { fs_type.to_uppercase() });
+                            ui.label(if device.is_empty() { "‚Äî".to_string() } else { device.clone() });
+                            ui.label(format_size(*total));
+                            ui.label(format_size(*used));
+                            ui.label(egui::RichText::new(format!("{:.1}%", percent)).color(color).strong());
+                            ui.end_row();
+                        }
+                    });
+            });
 
-                self.file_items.push(FileItem {
-                    path: entry_path,
-                    name,
-                    size,
-                    is_dir,
-                    category,
-                    usefulness,
-                    modified,
-                    child_count,
-                });
+        if let Some(col) = header_click {
+            if self.fs_sort_column == col {
+                self.fs_sort_desc = !self.fs_sort_desc;
+            } else {
+                self.fs_sort_column = col;
+                self.fs_sort_desc = true;
             }
         }
-
-        // Apply filtering and sorting
-        self.apply_filter_and_sort();
-        self.loading = false;
+        if let Some(mount) = row_click {
+            self.navigate_to(mount.clone());
+            self.current_disk = Some(mount);
+            self.file_items.clear();
+            self.search_query.clear();
+            self.view = AppView::Files;
+        }
     }
 
-    fn apply_filter_and_sort(&mut self) {
-        // Filter items based on search query
-        if self.search_query.is_empty() {
-            self.filtered_items = self.file_items.clone();
-        } else {
-            let query_lower = self.search_query.to_lowercase();
-            self.filtered_items = self.file_items.iter()
-                .filter(|item| {
-                    item.name.to_lowercase().contains(&query_lower) ||
-                    item.path.to_string_lossy().to_lowercase().contains(&query_lower)
-                })
-                .cloned()
-                .collect();
+    /// Invalidate cached folder sizes for `path` and every ancestor directory.
+    fn invalidate_size_ancestors(&mut self, path: &Path) {
+        let mut ancestor = path.parent();
+        while let Some(parent) = ancestor {
+            self.folder_size_cache.remove(parent);
+            self.pending_size_calculations.remove(parent);
+            ancestor = parent.parent();
         }
-        
-        // Apply sorting to filtered items
-        self.sort_file_items();
     }
 
-    fn apply_modern_theme(&self, ctx: &egui::Context) {
-        let mut style = (*ctx.style()).clone();
-
-        // Cyberpunk neon color palette
-        style.visuals.dark_mode = true;
-        style.visuals.panel_fill = egui::Color32::from_rgb(18, 16, 26);       // Dark purple
-        style.visuals.window_fill = egui::Color32::from_rgb(10, 10, 15);      // Deep black
-        style.visuals.extreme_bg_color = egui::Color32::from_rgb(8, 8, 12);   // Darker
-        style.visuals.faint_bg_color = egui::Color32::from_rgb(25, 22, 35);   // Purple tint
-        style.visuals.hyperlink_color = egui::Color32::from_rgb(0, 255, 255); // Neon cyan
+    /// Delete `paths` using the given mode (trash vs. permanent), skipping
+    /// protected system paths. Invalidates affected size caches and raises a
+    /// toast; when trashing, the toast offers an Undo that restores the items.
+    fn delete_paths(&mut self, paths: Vec<PathBuf>, to_trash: bool) {
+        let mut skipped = 0;
+        let targets: Vec<PathBuf> = paths
+            .into_iter()
+            .filter(|p| {
+                if is_protected_full_path(&p.to_string_lossy()) {
+                    skipped += 1;
+                    false
+                } else {
+                    true
+                }
+            })
+            .collect();
 
-        // Neon selection styling
-        style.visuals.button_frame = true;
-        style.visuals.selection.bg_fill = egui::Color32::from_rgb(80, 0, 120);  // Purple glow
-        style.visuals.selection.stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(0, 255, 255));
+        let (deleted, errors) = if to_trash {
+            let (deleted, new_items, errors) = trash_paths(&targets);
+            self.undo_trash = if new_items.is_empty() { None } else { Some(new_items) };
+            (deleted, errors)
+        } else {
+            self.undo_trash = None;
+            let mut deleted = 0;
+            let mut errors = Vec::new();
+            for path in &targets {
+                match delete_permanently(path) {
+                    Ok(_) => deleted += 1,
+                    Err(e) => errors.push(format!(
+                        "{}: {}",
+                        path.file_name().unwrap_or_default().to_string_lossy(),
+                        e
+                    )),
+                }
+            }
+            (deleted, errors)
+        };
 
-        // Widget styling with neon accents
-        style.visuals.widgets.inactive.bg_fill = egui::Color32::from_rgb(20, 18, 28);
-        style.visuals.widgets.inactive.bg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 40, 80));
-        style.visuals.widgets.hovered.bg_fill = egui::Color32::from_rgb(30, 25, 45);
-        style.visuals.widgets.hovered.bg_stroke = egui::Stroke::new(1.5, egui::Color32::from_rgb(0, 255, 255));
-        style.visuals.widgets.active.bg_fill = egui::Color32::from_rgb(50, 30, 70);
-        style.visuals.widgets.active.bg_stroke = egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 0, 255));
+        for path in &targets {
+            self.invalidate_size_ancestors(path);
+        }
+        self.selected_items.clear();
+        self.needs_refresh = true;
+
+        // When an Undo is available, advertise it regardless of how the count
+        // breaks down so the toast text agrees with the button that renders.
+        let undo = if self.undo_trash.is_some() { " ‚Äî Undo" } else { "" };
+        let verb = if to_trash { "Moved" } else { "Deleted" };
+        let dest = if to_trash { " to Recycle Bin" } else { " permanently" };
+        let ttl = if self.undo_trash.is_some() { 5.0 } else { 3.0 };
+        if !errors.is_empty() {
+            self.toast_message = Some((format!("‚ö†Ô∏è {} {} items, {} failed{}", verb, deleted, errors.len(), undo), ttl));
+        } else if skipped > 0 {
+            self.toast_message = Some((format!("üîí Skipped {} protected, {} {}{}", skipped, verb.to_lowercase(), deleted, undo), ttl));
+        } else {
+            self.toast_message = Some((format!("{} {} items{}{}", verb, deleted, dest, undo), ttl));
+        }
+    }
 
-        // Spacing
-        style.spacing.item_spacing = egui::Vec2::new(8.0, 6.0);
-        style.spacing.window_margin = egui::Margin::same(8.0);
+    /// Launch a cancellable background duplicate scan over `current_path`.
+    fn start_duplicate_scan(&mut self) {
+        let Some(root) = self.current_path.clone() else { return };
+        self.dup_cancel.store(true, Ordering::Relaxed); // stop any in-flight scan
+        self.duplicate_groups.clear();
+        self.view = AppView::Duplicates;
+        self.dup_scanning = true;
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.dup_cancel = cancel.clone();
+        while self.dup_done_receiver.try_recv().is_ok() {} // drop any prior scan's sentinel
+        let sender = self.dup_sender.clone();
+        let progress = self.dup_progress_sender.clone();
+        let done = self.dup_done_sender.clone();
+        self.dup_progress = None;
+        thread::spawn(move || {
+            scan_duplicates_reporting(&root, cancel, sender, Some(progress));
+            let _ = done.send(());
+        });
+    }
 
-        ctx.set_style(style);
+    /// Launch a background perceptual-hash scan over image files in `current_path`.
+    fn start_similar_image_scan(&mut self) {
+        let Some(root) = self.current_path.clone() else { return };
+        self.dup_cancel.store(true, Ordering::Relaxed);
+        self.similar_groups.clear();
+        self.view = AppView::SimilarImages;
+        self.sim_scanning = true;
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.dup_cancel = cancel.clone();
+        while self.sim_done_receiver.try_recv().is_ok() {} // drop any prior scan's sentinel
+        let sender = self.sim_sender.clone();
+        let done = self.sim_done_sender.clone();
+        let threshold = self.sim_threshold;
+        thread::spawn(move || {
+            let scan = || {
+                // Collect image paths under the tree.
+                let mut images = Vec::new();
+                let mut stack = vec![root];
+                while let Some(dir) = stack.pop() {
+                    if cancel.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    if let Ok(entries) = fs::read_dir(&dir) {
+                        for entry in entries.flatten() {
+                            let path = entry.path();
+                            if path.is_dir() {
+                                stack.push(path);
+                            } else if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+                                if PHOTO_EXTENSIONS.contains(&ext.to_lowercase().as_str()) {
+                                    images.push(path);
+                                }
+                            }
+                        }
+                    }
+                }
+                // Decode + hash in parallel, then cluster by Hamming distance.
+                use rayon::prelude::*;
+                let hashes: Vec<(PathBuf, u64)> = images
+                    .par_iter()
+                    .filter_map(|p| dhash_image_cached(p).map(|h| (p.clone(), h)))
+                    .collect();
+                for cluster in cluster_by_hamming(&hashes, threshold) {
+                    if cancel.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    let _ = sender.send(cluster);
+                }
+            };
+            scan();
+            let _ = done.send(());
+        });
     }
 
-    fn analyze_file(&self, path: &Path, name: &str, is_dir: bool, size: u64) -> (FileCategory, f32) {
-        let name_lower = name.to_lowercase();
-        let path_str = path.to_string_lossy().to_lowercase();
-
-        // System and critical files - NEVER delete these
-        if path_str.contains("windows\\system32") ||
-           path_str.contains("windows\\syswow64") ||
-           path_str.contains("program files") ||
-           path_str.contains("programdata") ||
-           name_lower == "windows" ||
-           name_lower == "boot" ||
-           name_lower == "bootmgr" ||
-           name_lower == "pagefile.sys" ||
-           name_lower == "hiberfil.sys" ||
-           name_lower == "$recycle.bin" ||
-           name_lower == "system volume information" ||
-           name_lower == "recovery" ||
-           name_lower.starts_with("$") {
-            return (FileCategory::MustKeep, 100.0);
-        }
-
-        // Temp files and cache - useless (safe to delete)
-        if name_lower.contains("temp") ||
-           name_lower.contains("cache") ||
-           name_lower.contains("tmp") ||
-           name_lower.ends_with(".tmp") ||
-           name_lower.ends_with(".log") ||
-           path_str.contains("\\temp\\") ||
-           path_str.contains("\\cache\\") ||
-           path_str.contains("\\tmp\\") ||
-           name_lower.starts_with("~$") {
-            return (FileCategory::Useless, 5.0);
-        }
-
-        // System files
-        if name_lower.ends_with(".sys") ||
-           name_lower.ends_with(".dll") ||
-           name_lower.ends_with(".exe") && path_str.contains("windows") ||
-           name_lower.ends_with(".inf") ||
-           name_lower.ends_with(".cat") {
-            return (FileCategory::System, 85.0);
-        }
-
-        // Get file extension for detailed analysis
-        let ext = path.extension()
-            .and_then(|e| e.to_str())
-            .map(|e| e.to_lowercase())
-            .unwrap_or_default();
+    /// Launch a cancellable background scan that groups audio files by acoustic
+    /// content (not tags), reusing the similar-results panel.
+    fn start_similar_audio_scan(&mut self) {
+        let Some(root) = self.current_path.clone() else { return };
+        self.dup_cancel.store(true, Ordering::Relaxed);
+        self.similar_groups.clear();
+        self.view = AppView::SimilarAudio;
+        self.sim_scanning = true;
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.dup_cancel = cancel.clone();
+        while self.sim_done_receiver.try_recv().is_ok() {} // drop any prior scan's sentinel
+        let sender = self.sim_sender.clone();
+        let done = self.sim_done_sender.clone();
+        let threshold = self.sim_audio_threshold;
+        thread::spawn(move || {
+            let scan = || {
+                // Collect audio paths under the tree.
+                let mut tracks = Vec::new();
+                let mut stack = vec![root];
+                while let Some(dir) = stack.pop() {
+                    if cancel.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    if let Ok(entries) = fs::read_dir(&dir) {
+                        for entry in entries.flatten() {
+                            let path = entry.path();
+                            if path.is_dir() {
+                                stack.push(path);
+                            } else if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+                                if AUDIO_EXTENSIONS.contains(&ext.to_lowercase().as_str()) {
+                                    tracks.push(path);
+                                }
+                            }
+                        }
+                    }
+                }
+                // Fingerprint in parallel (cached by path+mtime), then cluster.
+                use rayon::prelude::*;
+                let prints: Vec<(PathBuf, Vec<u16>)> = tracks
+                    .par_iter()
+                    .filter_map(|p| audio_fingerprint_cached(p).map(|fp| (p.clone(), fp)))
+                    .collect();
+                for cluster in cluster_fingerprints(&prints, threshold) {
+                    if cancel.load(Ordering::Relaxed) {
+                        return;
+                    }
+                    let _ = sender.send(cluster);
+                }
+            };
+            scan();
+            let _ = done.send(());
+        });
+    }
 
-        // Important user data - high usefulness
-        let important_extensions = ["doc", "docx", "xls", "xlsx", "ppt", "pptx", "pdf",
-                                    "txt", "md", "rtf", "odt", "ods", "odp"];
-        if important_extensions.contains(&ext.as_str()) {
-            return (FileCategory::Regular, 90.0);
-        }
+    fn render_similar_audio_view(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading(egui::RichText::new("üéµ Similar Audio")
+                .size(22.0)
+                .color(egui::Color32::from_rgb(0, 255, 255)));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if self.sim_scanning && ui.button("‚è¹ Stop").clicked() {
+                    self.dup_cancel.store(true, Ordering::Relaxed);
+                    self.sim_scanning = false;
+                }
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Max distance:");
+            ui.add(egui::Slider::new(&mut self.sim_audio_threshold, 0.0..=0.5));
+        });
+        ui.label(egui::RichText::new(format!(
+            "{} groups{}", self.similar_groups.len(),
+            if self.sim_scanning { " (scanning‚Ä¶)" } else { "" }))
+            .color(egui::Color32::from_rgb(120, 100, 160)));
+        ui.separator();
 
-        // Photos - very important to users
-        let photo_extensions = ["jpg", "jpeg", "png", "gif", "bmp", "webp", "raw", "cr2", "nef", "arw"];
-        if photo_extensions.contains(&ext.as_str()) {
-            return (FileCategory::Regular, 95.0);
+        egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                for group in self.similar_groups.clone() {
+                    egui::Frame::default()
+                        .fill(egui::Color32::from_rgb(20, 18, 28))
+                        .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 40, 80)))
+                        .rounding(6.0)
+                        .inner_margin(egui::Margin::same(10.0))
+                        .show(ui, |ui| {
+                            ui.label(egui::RichText::new(format!("{} similar tracks", group.len()))
+                                .strong()
+                                .color(egui::Color32::from_rgb(0, 212, 255)));
+                            for path in &group {
+                                let selected = self.selected_items.contains(path);
+                                if ui.selectable_label(selected, path.to_string_lossy()).clicked() {
+                                    if selected {
+                                        self.selected_items.remove(path);
+                                    } else {
+                                        self.selected_items.insert(path.clone());
+                                    }
+                                }
+                            }
+                        });
+                    ui.add_space(6.0);
+                }
+            });
+    }
+
+    fn render_similar_images_view(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.heading(egui::RichText::new("üñº Similar Images")
+                .size(22.0)
+                .color(egui::Color32::from_rgb(0, 255, 255)));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if self.sim_scanning && ui.button("‚è¹ Stop").clicked() {
+                    self.dup_cancel.store(true, Ordering::Relaxed);
+                    self.sim_scanning = false;
+                }
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.label("Threshold:");
+            ui.add(egui::Slider::new(&mut self.sim_threshold, 0..=20));
+        });
+        ui.label(egui::RichText::new(format!(
+            "{} groups{}", self.similar_groups.len(),
+            if self.sim_scanning { " (scanning‚Ä¶)" } else { "" }))
+            .color(egui::Color32::from_rgb(120, 100, 160)));
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                for group in self.similar_groups.clone() {
+                    egui::Frame::default()
+                        .fill(egui::Color32::from_rgb(20, 18, 28))
+                        .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 40, 80)))
+                        .rounding(6.0)
+                        .inner_margin(egui::Margin::same(10.0))
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(format!(
+                                    "{} similar images  ‚Ä¢  {:.0}% alike",
+                                    group.len(),
+                                    group_similarity_percent(&group)))
+                                    .strong()
+                                    .color(egui::Color32::from_rgb(0, 212, 255)));
+                                // Keep the highest-quality (largest) copy, queue the rest.
+                                if ui.button("Select lower-quality copies").clicked() {
+                                    for path in lower_quality_image_copies(&group) {
+                                        self.selected_items.insert(path);
+                                    }
+                                }
+                            });
+                            for path in &group {
+                                let selected = self.selected_items.contains(path);
+                                let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+                                let label = format!("{}  ‚Ä¢  {}", path.to_string_lossy(), format_size(size));
+                                if ui.selectable_label(selected, label).clicked() {
+                                    if selected {
+                                        self.selected_items.remove(path);
+                                    } else {
+                                        self.selected_items.insert(path.clone());
+                                    }
+                                }
+                            }
+                        });
+                    ui.add_space(6.0);
+                }
+            });
+    }
+
+    /// Mark every group's redundant copies for deletion under `strategy`,
+    /// feeding them into the existing Delete Selected flow.
+    fn select_duplicates_by_strategy(&mut self, strategy: DuplicateStrategy) {
+        let groups = self.duplicate_groups.clone();
+        self.selected_items.clear();
+        let mut n = 0;
+        for group in &groups {
+            for path in resolve_duplicates(group, strategy) {
+                if self.selected_items.insert(path) {
+                    n += 1;
+                }
+            }
         }
+        self.toast_message = Some((format!("Selected {} duplicate copies", n), 2.5));
+    }
 
-        // Videos - important but large
-        let video_extensions = ["mp4", "avi", "mkv", "mov", "wmv", "flv", "webm", "m4v"];
-        if video_extensions.contains(&ext.as_str()) {
-            // Larger videos slightly less useful (more likely to be deletable)
-            let usefulness = if size > 1_000_000_000 { 70.0 } else { 85.0 };
-            return (FileCategory::Regular, usefulness);
+    fn render_duplicates_view(&mut self, ui: &mut egui::Ui) {
+        let total = total_reclaimable(&self.duplicate_groups);
+        ui.horizontal(|ui| {
+            ui.heading(egui::RichText::new("üß¨ Duplicate Files")
+                .size(22.0)
+                .color(egui::Color32::from_rgb(0, 255, 255)));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if self.dup_scanning && ui.button("‚è¹ Stop").clicked() {
+                    self.dup_cancel.store(true, Ordering::Relaxed);
+                    self.dup_scanning = false;
+                }
+                // Queue every redundant copy (keeping one per group) for the
+                // existing Delete Selected flow, skipping protected paths.
+                if !self.duplicate_groups.is_empty()
+                    && ui.button("‚úÖ Select duplicates (keep one)").clicked()
+                {
+                    for path in duplicate_deletion_candidates(&self.duplicate_groups) {
+                        self.selected_items.insert(path);
+                    }
+                    let n = self.selected_items.len();
+                    self.toast_message = Some((format!("Selected {} duplicate copies", n), 2.5));
+                }
+                // Mtime-aware strategies: keep only the newest/oldest copy per group.
+                if !self.duplicate_groups.is_empty() {
+                    if ui.button("Keep oldest").clicked() {
+                        self.select_duplicates_by_strategy(DuplicateStrategy::KeepOneOldest);
+                    }
+                    if ui.button("Keep newest").clicked() {
+                        self.select_duplicates_by_strategy(DuplicateStrategy::KeepOneNewest);
+                    }
+                }
+            });
+        });
+        ui.label(egui::RichText::new(format!(
+            "{} groups ‚Ä¢ {} reclaimable{}",
+            self.duplicate_groups.len(),
+            format_size(total),
+            if self.dup_scanning { " (scanning‚Ä¶)" } else { "" }))
+            .color(egui::Color32::from_rgb(120, 100, 160)));
+        // Staged progress bar while a scan is running.
+        if self.dup_scanning {
+            if let Some(p) = self.dup_progress {
+                let stage_name = ProgressData::DUPLICATE_STAGES
+                    .get((p.current_stage as usize).saturating_sub(1))
+                    .copied()
+                    .unwrap_or("");
+                ui.add(egui::ProgressBar::new(p.fraction()).text(format!(
+                    "{} ({}/{})",
+                    stage_name, p.current_stage, p.max_stage
+                )));
+            }
         }
+        ui.separator();
+
+        egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                for group in self.duplicate_groups.clone() {
+                    egui::Frame::default()
+                        .fill(egui::Color32::from_rgb(20, 18, 28))
+                        .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 40, 80)))
+                        .rounding(6.0)
+                        .inner_margin(egui::Margin::same(10.0))
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new(format!(
+                                    "{} copies ‚Ä¢ {} each ‚Ä¢ reclaim {}",
+                                    group.paths.len(),
+                                    format_size(group.size),
+                                    format_size(group.reclaimable())))
+                                    .strong()
+                                    .color(egui::Color32::from_rgb(0, 212, 255)));
+                                // Select every copy but the first for deletion/trashing.
+                                if ui.button("Select all but one").clicked() {
+                                    for path in group.paths.iter().skip(1) {
+                                        self.selected_items.insert(path.clone());
+                                    }
+                                }
+                            });
+                            for path in &group.paths {
+                                let selected = self.selected_items.contains(path);
+                                if ui.selectable_label(selected, path.to_string_lossy()).clicked() {
+                                    if selected {
+                                        self.selected_items.remove(path);
+                                    } else {
+                                        self.selected_items.insert(path.clone());
+                                    }
+                                }
+                            }
+                        });
+                    ui.add_space(6.0);
+                }
+            });
+    }
 
-        // Audio - important
-        let audio_extensions = ["mp3", "wav", "flac", "ogg", "aac", "m4a", "wma"];
-        if audio_extensions.contains(&ext.as_str()) {
-            return (FileCategory::Regular, 80.0);
+    /// Reload the cached trash listing from the OS trash.
+    fn refresh_trash(&mut self) {
+        self.selected_trash = None;
+        self.trash_items.clear();
+        if let Ok(items) = trash::os_limited::list() {
+            for item in items {
+                let original_path = item.original_path();
+                let name = item.name.to_string_lossy().to_string();
+                let deleted = Some(SystemTime::UNIX_EPOCH
+                    + std::time::Duration::from_secs(item.time_deleted.max(0) as u64));
+                let size = trash::os_limited::metadata(&item)
+                    .ok()
+                    .map(|m| match m.size {
+                        trash::TrashItemSize::Bytes(b) => b,
+                        trash::TrashItemSize::Entries(_) => 0,
+                    })
+                    .unwrap_or(0);
+                self.trash_items.push(TrashItem {
+                    id: item,
+                    name,
+                    original_path,
+                    deleted,
+                    size,
+                });
+            }
         }
+    }
+
+    fn render_trash_view(&mut self, ui: &mut egui::Ui) {
+        ui.heading(egui::RichText::new("‚ôªÔ∏è Recycle Bin")
+            .size(22.0)
+            .color(egui::Color32::from_rgb(255, 0, 255)));
+        ui.horizontal(|ui| {
+            if ui.button("üîÑ Refresh").clicked() {
+                self.refresh_trash();
+            }
+            if let Some(idx) = self.selected_trash {
+                if ui.add(egui::Button::new("‚ôªÔ∏è Restore")
+                    .fill(egui::Color32::from_rgb(30, 60, 40)))
+                    .clicked()
+                {
+                    let item = self.trash_items.remove(idx);
+                    self.selected_trash = None;
+                    match trash::os_limited::restore_all([item.id]) {
+                        Ok(_) => self.toast_message = Some((format!("‚ôªÔ∏è Restored {}", item.name), 2.0)),
+                        Err(e) => self.delete_error = Some(format!("Failed to restore: {}", e)),
+                    }
+                }
+                if ui.add(egui::Button::new("üóëÔ∏è Purge")
+                    .fill(egui::Color32::from_rgb(120, 40, 40)))
+                    .clicked()
+                {
+                    let item = self.trash_items.remove(idx);
+                    self.selected_trash = None;
+                    match trash::os_limited::purge_all([item.id]) {
+                        Ok(_) => self.toast_message = Some((format!("üóëÔ∏è Purged {}", item.name), 2.0)),
+                        Err(e) => self.delete_error = Some(format!("Failed to purge: {}", e)),
+                    }
+                }
+            }
+        });
+        ui.separator();
 
-        // Code and projects - important for developers
-        let code_extensions = ["rs", "py", "js", "ts", "java", "c", "cpp", "h", "cs", "go",
-                              "html", "css", "json", "xml", "yaml", "toml", "sql"];
-        if code_extensions.contains(&ext.as_str()) {
-            return (FileCategory::Regular, 85.0);
+        if self.trash_items.is_empty() {
+            ui.centered_and_justified(|ui| {
+                ui.label(egui::RichText::new("Recycle Bin is empty")
+                    .color(egui::Color32::from_rgb(120, 100, 160)));
+            });
+            return;
         }
 
-        // Archives - depends on size, often can be deleted after extraction
-        let archive_extensions = ["zip", "rar", "7z", "tar", "gz", "bz2"];
-        if archive_extensions.contains(&ext.as_str()) {
-            let usefulness = if size > 1_000_000_000 { 30.0 }  // >1GB - likely can delete
-                            else if size > 100_000_000 { 45.0 }  // >100MB
-                            else { 55.0 };
-            return (FileCategory::Regular, usefulness);
+        egui::ScrollArea::vertical()
+            .auto_shrink([false; 2])
+            .show(ui, |ui| {
+                let now = SystemTime::now();
+                for (idx, item) in self.trash_items.clone().iter().enumerate() {
+                    let is_selected = self.selected_trash == Some(idx);
+                    let fill = if is_selected {
+                        egui::Color32::from_rgb(60, 20, 80)
+                    } else {
+                        egui::Color32::from_rgb(18, 16, 26)
+                    };
+                    let response = egui::Frame::default()
+                        .fill(fill)
+                        .stroke(egui::Stroke::new(1.0, egui::Color32::from_rgb(80, 0, 120)))
+                        .rounding(6.0)
+                        .inner_margin(egui::Margin::same(10.0))
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(egui::RichText::new("‚ôªÔ∏è").size(18.0));
+                                ui.add_space(8.0);
+                                ui.vertical(|ui| {
+                                    ui.label(egui::RichText::new(&item.name)
+                                        .size(13.0)
+                                        .color(egui::Color32::from_rgb(200, 180, 255)));
+                                    ui.label(egui::RichText::new(item.original_path.to_string_lossy())
+                                        .size(10.0)
+                                        .color(egui::Color32::from_gray(140)));
+                                });
+                                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                    ui.label(egui::RichText::new(format_size(item.size))
+                                        .size(11.0)
+                                        .color(egui::Color32::from_gray(160)));
+                                    if let Some(deleted) = item.deleted {
+                                        if let Ok(age) = now.duration_since(deleted) {
+                                            ui.add_space(12.0);
+                                            ui.label(egui::RichText::new(format!("{} days ago", age.as_secs() / 86400))
+                                                .size(11.0)
+                                                .color(egui::Color32::from_gray(140)));
+                                        }
+                                    }
+                                });
+                            });
+                        });
+                    let interact = ui.interact(response.response.rect,
+                        ui.make_persistent_id(("trash_item", idx)), egui::Sense::click());
+                    if interact.clicked() {
+                        self.selected_trash = Some(idx);
+                    }
+                    ui.add_space(4.0);
+                }
+            });
+    }
+
+    fn load_directory(&mut self, path: &Path) {
+        self.loading = true;
+        self.file_items.clear();
+
+        // Pick up any `.gitignore`/`.ignore` in the directory being scanned so
+        // ignored children are dropped from the listing; `.gitignore` wins when
+        // both are present.
+        let gitignore = IgnoreSet::from_file(&path.join(".gitignore"));
+        self.ignore_set = if gitignore.is_empty() {
+            IgnoreSet::from_file(&path.join(".ignore"))
+        } else {
+            gitignore
+        };
+
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                if let Some(item) = self.build_file_item(entry.path(), entry.metadata().ok()) {
+                    self.file_items.push(item);
+                }
+            }
         }
 
-        // ISOs and disk images - usually can be deleted
-        if ext == "iso" || ext == "dmg" || ext == "img" {
-            return (FileCategory::Regular, 25.0);
+        // Apply filtering and sorting
+        self.apply_filter_and_sort();
+        self.loading = false;
+    }
+
+    /// Build a [`FileItem`] for one directory entry, applying the scan-time skip
+    /// rules. Returns `None` when the entry is filtered out. Shared by the full
+    /// [`load_directory`] scan and the watcher's incremental refresh.
+    fn build_file_item(&mut self, entry_path: PathBuf, metadata: Option<fs::Metadata>) -> Option<FileItem> {
+        let is_dir = metadata.as_ref().map(|m| m.is_dir()).unwrap_or(false);
+        let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+
+        let name = entry_path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("Unknown")
+            .to_string();
+
+        // Drop excluded extensions and path substrings before they ever
+        // enter the listing; directories stay so the tree is navigable.
+        if should_skip_scan_entry(
+            &entry_path,
+            &name,
+            is_dir,
+            &self.allowed_extensions,
+            &self.excluded_extensions,
+            &self.excluded_paths,
+        ) {
+            return None;
         }
 
-        // Executables and installers - often safe to delete after install
-        let installer_extensions = ["exe", "msi", "bat", "cmd", "ps1"];
-        if installer_extensions.contains(&ext.as_str()) {
-            // Installers in Downloads are less useful
-            if path_str.contains("downloads") {
-                return (FileCategory::Regular, 35.0);
-            }
-            return (FileCategory::Regular, 60.0);
+        // Honour the directory's `.gitignore`/`.ignore` rules; children are
+        // matched by name since they sit directly under the scan root.
+        if !self.ignore_set.is_empty() && self.ignore_set.is_ignored(&name, is_dir) {
+            return None;
         }
 
-        // Old backup files
-        if name_lower.ends_with(".bak") || name_lower.ends_with(".old") || name_lower.contains("backup") {
-            return (FileCategory::Regular, 40.0);
+        // Calculate size: for files use metadata, for dirs calculate recursive size
+        let (size, child_count) = if is_dir {
+            let count = fs::read_dir(&entry_path).ok().map(|rd| rd.count());
+            // Use cached recursive size or calculate it
+            let dir_size = self.get_folder_size_recursive(&entry_path);
+            (dir_size, count)
+        } else {
+            (metadata.as_ref().map(|m| m.len()).unwrap_or(0), None)
+        };
+
+        let (mut category, mut usefulness) = self.analyze_file(&entry_path, &name, is_dir, size);
+
+        // A directory with no children is a degenerate entry a cleanup scan can
+        // reclaim; tag it here where the child count is known.
+        if is_dir && child_count == Some(0) {
+            category = FileCategory::EmptyFolder;
+            usefulness = DEGENERATE_USEFULNESS;
         }
 
-        // Folders - base usefulness on contents
-        if is_dir {
-            // Node modules, build folders - low usefulness
-            if name_lower == "node_modules" || name_lower == "target" ||
-               name_lower == "build" || name_lower == "dist" || name_lower == ".git" {
-                return (FileCategory::Regular, 30.0);
+        let excluded = path_excluded(&self.exclude_globs, &entry_path);
+
+        // Flag disguised files whose real format contradicts their extension.
+        // Only files that *claim* a signature-bearing format are sniffed, so
+        // the common text/source case never opens the file.
+        let mut real_type = None;
+        if !is_dir && category == FileCategory::Regular {
+            let ext = entry_path
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("");
+            if declares_known_signature(ext) {
+                if let ExtensionVerdict::Mismatch { real, .. } =
+                    verify_extension(&entry_path.to_string_lossy(), ext)
+                {
+                    category = FileCategory::Suspicious;
+                    usefulness = (usefulness * 0.3).min(20.0);
+                    real_type = Some(real);
+                }
             }
-            // User folders - high usefulness
-            if name_lower == "documents" || name_lower == "pictures" ||
-               name_lower == "music" || name_lower == "videos" {
-                return (FileCategory::Regular, 95.0);
+        }
+
+        Some(FileItem {
+            path: entry_path,
+            name,
+            size,
+            is_dir,
+            category,
+            usefulness,
+            modified,
+            child_count,
+            media_info: None,
+            excluded,
+            real_type,
+        })
+    }
+
+    /// Merge watched filesystem changes into the current listing without a full
+    /// reload, preserving `selected_items`/`selection_anchor`. Only the direct
+    /// children of the current directory that an event touched are re-stat'd and
+    /// re-categorized; their updated size and [`FileCategory`] replace the stale
+    /// entry in place (or the entry is dropped when the child no longer exists).
+    fn refresh_changed_entries(&mut self, changed: &[PathBuf]) {
+        let Some(dir) = self.current_path.clone() else { return };
+
+        // Reduce the raw (possibly deep) event paths to the set of direct
+        // children of the current directory they fall under.
+        let mut affected: HashSet<PathBuf> = HashSet::new();
+        for p in changed {
+            if let Ok(rel) = p.strip_prefix(&dir) {
+                if let Some(first) = rel.components().next() {
+                    affected.insert(dir.join(first.as_os_str()));
+                }
             }
-            // Downloads - medium, often contains deletable files
-            if name_lower == "downloads" {
-                return (FileCategory::Regular, 50.0);
+        }
+        if affected.is_empty() {
+            // The change was to the directory itself; fall back to a reload.
+            self.needs_refresh = true;
+            return;
+        }
+
+        for child in affected {
+            self.file_items.retain(|i| i.path != child);
+            // Drop any cached size so the rebuilt entry reflects the change, and
+            // mirror load_directory's non-following metadata for consistent typing.
+            self.folder_size_cache.remove(&child);
+            self.pending_size_calculations.remove(&child);
+            if let Some(item) = self.build_file_item(child.clone(), fs::symlink_metadata(&child).ok()) {
+                self.file_items.push(item);
             }
-            // Default folder usefulness
-            return (FileCategory::Regular, 65.0);
         }
+        self.apply_filter_and_sort();
+    }
+
+    fn apply_filter_and_sort(&mut self) {
+        // Parse the search box once: a plain word is a case-insensitive
+        // substring, but globs, `/regex/`, and `ext:`/`-ext:` lists are honoured
+        // too (see [`FilterSpec`]).
+        let has_query = !self.search_query.trim().is_empty();
+        let spec = FilterSpec::parse(&self.search_query);
+        self.filtered_items = self.file_items.iter()
+            .filter(|item| !has_query || spec.matches(item))
+            .filter(|item| passes_extension_filter(
+                &item.name,
+                item.is_dir,
+                item.size,
+                &self.allowed_extensions,
+                &self.excluded_extensions,
+                self.min_size_filter,
+            ))
+            .filter(|item| passes_type_filter(&item.name, item.is_dir, &self.active_type_filters))
+            .filter(|item| !self.only_useless || item.category == FileCategory::Useless)
+            .filter(|item| !self.only_degenerate || item.category.is_degenerate())
+            // Glob-excluded items are dimmed in place, or dropped outright when
+            // the "hide excluded" toggle is on.
+            .filter(|item| !self.hide_excluded || !item.excluded)
+            .cloned()
+            .collect();
+
+        // Apply sorting to filtered items
+        self.sort_file_items();
+    }
+
+    /// Populate the include filter with the "useless" extension preset
+    /// (logs/temp/cache artifacts that are usually safe to reclaim).
+    fn apply_useless_preset(&mut self) {
+        self.allowed_ext_input = "log,tmp,temp,cache,bak,old".to_string();
+        self.allowed_extensions = parse_extension_list(&self.allowed_ext_input);
+        self.apply_filter_and_sort();
+    }
 
-        // Default for unknown files - base on size
-        let usefulness = if size > 500_000_000 { 45.0 }  // >500MB - might want to check
-                        else if size > 100_000_000 { 55.0 }  // >100MB
-                        else { 60.0 };
-        (FileCategory::Regular, usefulness)
+    fn apply_modern_theme(&self, ctx: &egui::Context) {
+        let mut style = (*ctx.style()).clone();
+
+        // Cyberpunk neon color palette
+        style.visuals.dark_mode = true;
+        style.visuals.panel_fill = egui::Color32::from_rgb(18, 16, 26);       // Dark purple
+        style.visuals.window_fill = egui::Color32::from_rgb(10, 10, 15);      // Deep black
+        style.visuals.extreme_bg_color = egui::Color32::from_rgb(8, 8, 12);   // Darker
+        style.visuals.faint_bg_color = egui::Color32::from_rgb(25, 22, 35);   // Purple tint
+        style.visuals.hyperlink_color = egui::Color32::from_rgb(0, 255, 255); // Neon cyan
+
+        // Neon selection styling
+        style.visuals.button_frame = true;
+        style.visuals.selection.bg_fill = egui::Color32::from_rgb(80, 0, 120);  // Purple glow
+        style.visuals.selection.stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(0, 255, 255));
+
+        // Widget styling with neon accents
+        style.visuals.widgets.inactive.bg_fill = egui::Color32::from_rgb(20, 18, 28);
+        style.visuals.widgets.inactive.bg_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(60, 40, 80));
+        style.visuals.widgets.hovered.bg_fill = egui::Color32::from_rgb(30, 25, 45);
+        style.visuals.widgets.hovered.bg_stroke = egui::Stroke::new(1.5, egui::Color32::from_rgb(0, 255, 255));
+        style.visuals.widgets.active.bg_fill = egui::Color32::from_rgb(50, 30, 70);
+        style.visuals.widgets.active.bg_stroke = egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 0, 255));
+
+        // Spacing
+        style.spacing.item_spacing = egui::Vec2::new(8.0, 6.0);
+        style.spacing.window_margin = egui::Margin::same(8.0);
+
+        ctx.set_style(style);
+    }
+
+    fn analyze_file(&self, path: &Path, name: &str, is_dir: bool, size: u64) -> (FileCategory, f32) {
+        // Classification is driven by the shared, config-loadable ruleset so a
+        // user-supplied `classification.json` actually retunes the live scan.
+        categorize_file(&path.to_string_lossy(), name, is_dir, size)
     }
 
     fn sort_file_items(&mut self) {
@@ -975,7 +2387,11 @@ impl DiskDashboard {
             }
 
             let ordering = match self.sort_column {
-                SortColumn::Name => a.name.cmp(&b.name),
+                SortColumn::Name => if self.natural_sort {
+                    natural_cmp(&a.name, &b.name)
+                } else {
+                    a.name.cmp(&b.name)
+                },
                 SortColumn::Size => a.size.cmp(&b.size),
                 SortColumn::Category => {
                     let a_val = a.category as u8;
@@ -983,6 +2399,7 @@ impl DiskDashboard {
                     a_val.cmp(&b_val)
                 },
                 SortColumn::Usefulness => a.usefulness.partial_cmp(&b.usefulness).unwrap_or(std::cmp::Ordering::Equal),
+                SortColumn::DuplicateGroup => a.size.cmp(&b.size).then_with(|| a.name.cmp(&b.name)),
             };
 
             match self.sort_direction {
@@ -1002,9 +2419,48 @@ impl DiskDashboard {
                 self.history_index = self.navigation_history.len() - 1;
             }
         }
+        // A new path invalidates in-flight size scans.
+        self.cancel_size_scans();
+        self.watch_path(&path);
+        // Keep a short most-recently-visited list, newest first, deduped.
+        self.recent_dirs.retain(|p| p != &path);
+        self.recent_dirs.insert(0, path.clone());
+        self.recent_dirs.truncate(8);
         self.current_path = Some(path);
     }
 
+    /// Pin the given directory to the persistent bookmark list.
+    fn add_bookmark(&mut self, path: PathBuf) {
+        if !self.bookmarks.contains(&path) {
+            self.bookmarks.push(path);
+            save_bookmarks(&self.bookmarks);
+        }
+    }
+
+    /// Register a recursive filesystem watcher on `path`, dropping the watcher
+    /// for any previously-watched directory to bound OS watch handles.
+    fn watch_path(&mut self, path: &Path) {
+        use notify::{RecursiveMode, Watcher};
+        if self.watched_path.as_deref() == Some(path) {
+            return;
+        }
+        // Dropping the old watcher unregisters the previous path.
+        self.watcher = None;
+        let sender = self.watch_sender.clone();
+        let mut watcher = match notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = sender.send(event);
+            }
+        }) {
+            Ok(w) => w,
+            Err(_) => return,
+        };
+        if watcher.watch(path, RecursiveMode::Recursive).is_ok() {
+            self.watcher = Some(watcher);
+            self.watched_path = Some(path.to_path_buf());
+        }
+    }
+
     fn navigate_back(&mut self) {
         if self.history_index > 0 {
             self.history_index -= 1;
@@ -1037,17 +2493,110 @@ impl DiskDashboard {
             return 0; // Return 0 while calculating
         }
 
-        // Start async calculation
+        // Respect the worker-pool size: if every worker is busy, leave the
+        // directory unscheduled and retry on a later frame (it is not marked
+        // pending, so the next draw re-attempts it).
+        if self.size_workers_active.load(Ordering::Relaxed) >= self.size_worker_threads.max(1) {
+            return 0;
+        }
+
+        // Start async calculation, tagged with the current generation so stale
+        // results can be discarded and the worker can be cancelled. Workers run
+        // with an enlarged stack to survive very deep directory hierarchies.
         let path_buf = path.to_path_buf();
         let sender = self.size_sender.clone();
+        let cancel = self.size_cancel.clone();
+        let generation = self.scan_generation;
+        let active = self.size_workers_active.clone();
+        let excludes = self.exclude_globs.clone();
         self.pending_size_calculations.insert(path_buf.clone());
+        active.fetch_add(1, Ordering::Relaxed);
+
+        let spawn = thread::Builder::new()
+            .name("size-scan".into())
+            .stack_size(8 * 1024 * 1024)
+            .spawn(move || {
+                let mut files = 0u64;
+                let mut bytes = 0u64;
+                // Walk the whole subtree now that sizing runs off the UI thread;
+                // cancellation keeps navigating away responsive.
+                let size = calculate_dir_size_streaming(
+                    &path_buf, SIZE_SCAN_MAX_DEPTH, &cancel, generation, &sender, &mut files, &mut bytes, &excludes);
+                if !cancel.load(Ordering::Relaxed) {
+                    let _ = sender.send(SizeUpdate::Complete { generation, path: path_buf, size });
+                }
+                active.fetch_sub(1, Ordering::Relaxed);
+            });
+        // If the OS refused the thread, undo the bookkeeping so it retries.
+        if spawn.is_err() {
+            self.size_workers_active.fetch_sub(1, Ordering::Relaxed);
+            self.pending_size_calculations.remove(path);
+        }
 
+        0 // Return 0 while calculating
+    }
+
+    /// Abort any in-flight size scans and bump the generation so their pending
+    /// results are ignored. Called whenever the browsed path changes.
+    fn cancel_size_scans(&mut self) {
+        self.size_cancel.store(true, Ordering::Relaxed);
+        self.size_cancel = Arc::new(AtomicBool::new(false));
+        self.scan_generation = self.scan_generation.wrapping_add(1);
+        self.pending_size_calculations.clear();
+        self.scan_progress = None;
+    }
+
+    /// Feature-detect ffprobe once, caching the result. Returns whether the
+    /// binary responded to `-version`.
+    fn ensure_ffprobe_detected(&mut self) -> bool {
+        if let Some(available) = self.ffprobe_available {
+            return available;
+        }
+        let available = std::process::Command::new("ffprobe")
+            .arg("-version")
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+        self.ffprobe_available = Some(available);
+        available
+    }
+
+    /// Ensure a media file's metadata is available, serving a cache hit
+    /// immediately or spawning a background ffprobe probe otherwise.
+    ///
+    /// Returns the cached [`MediaInfo`] when present so the caller can render
+    /// it this frame; otherwise returns `None` and schedules the probe.
+    fn request_media_info(&mut self, path: &Path, mtime: Option<SystemTime>) -> Option<MediaInfo> {
+        let key = (path.to_path_buf(), mtime);
+        if let Some(info) = self.media_cache.get(&key) {
+            return Some(info.clone());
+        }
+        if !self.ensure_ffprobe_detected() || self.media_pending.contains(path) {
+            return None;
+        }
+        self.media_pending.insert(path.to_path_buf());
+        let sender = self.media_sender.clone();
+        let path_buf = path.to_path_buf();
         thread::spawn(move || {
-            let size = calculate_dir_size_recursive(&path_buf);
-            let _ = sender.send((path_buf, size));
+            let output = std::process::Command::new("ffprobe")
+                .args([
+                    "-v", "quiet",
+                    "-print_format", "json",
+                    "-show_format",
+                    "-show_streams",
+                ])
+                .arg(&path_buf)
+                .output();
+            let info = output
+                .ok()
+                .filter(|o| o.status.success())
+                .and_then(|o| parse_ffprobe_json(&String::from_utf8_lossy(&o.stdout)))
+                .unwrap_or_default();
+            let _ = sender.send(MediaUpdate { path: path_buf, mtime, info });
         });
-
-        0 // Return 0 while calculating
+        None
     }
 
     fn render_file_browser(&mut self, ui: &mut egui::Ui, current_path: &Path) {
@@ -1111,9 +2660,187 @@ impl DiskDashboard {
                     if !self.search_query.is_empty() {
                         ui.label(format!("({} results)", self.filtered_items.len()));
                     }
-                });
-            });
-        
+
+                    if ui.add(egui::Button::new("üß¨ Find Duplicates")
+                        .fill(egui::Color32::from_rgb(30, 45, 60)))
+                        .clicked()
+                    {
+                        self.start_duplicate_scan();
+                    }
+
+                    if ui.add(egui::Button::new("üñº Similar Images")
+                        .fill(egui::Color32::from_rgb(30, 45, 60)))
+                        .clicked()
+                    {
+                        self.start_similar_image_scan();
+                    }
+
+                    if ui.add(egui::Button::new("üéµ Similar Audio")
+                        .fill(egui::Color32::from_rgb(30, 45, 60)))
+                        .clicked()
+                    {
+                        self.start_similar_audio_scan();
+                    }
+
+                    if ui.add(egui::Button::new("üéõ Filters")
+                        .fill(egui::Color32::from_rgb(35, 30, 55)))
+                        .clicked()
+                    {
+                        self.show_filter_settings = !self.show_filter_settings;
+                    }
+
+                    // Live scan progress + stop control.
+                    if let Some((files, bytes)) = self.scan_progress {
+                        ui.spinner();
+                        ui.label(egui::RichText::new(format!(
+                            "Scanning‚Ä¶ {} files, {}", files, format_size(bytes)))
+                            .size(11.0)
+                            .color(egui::Color32::from_rgb(0, 255, 255)));
+                        if ui.button("‚è¹ Stop").clicked() {
+                            self.cancel_size_scans();
+                        }
+                    }
+                });
+
+                // Type-group filter chips, intersected with the text query.
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(egui::RichText::new("Type:")
+                        .size(11.0)
+                        .color(egui::Color32::from_rgb(120, 100, 160)));
+                    for (group, label) in FileTypeGroup::ALL {
+                        let active = self.active_type_filters.contains(&group);
+                        if ui.selectable_label(active, label).clicked() {
+                            if active {
+                                self.active_type_filters.remove(&group);
+                            } else {
+                                self.active_type_filters.insert(group);
+                            }
+                            self.apply_filter_and_sort();
+                        }
+                    }
+                    ui.separator();
+                    if ui.selectable_label(self.only_useless, "Useless only").clicked() {
+                        self.only_useless = !self.only_useless;
+                        self.apply_filter_and_sort();
+                    }
+                    if ui.selectable_label(self.only_degenerate, "Empty & broken only").clicked() {
+                        self.only_degenerate = !self.only_degenerate;
+                        self.apply_filter_and_sort();
+                    }
+                    // Removable summary chips for the active filters + result count.
+                    if !self.active_type_filters.is_empty() || self.only_useless || self.only_degenerate {
+                        ui.separator();
+                        if ui.button(format!("‚úñ Clear ({} shown)", self.filtered_items.len())).clicked() {
+                            self.active_type_filters.clear();
+                            self.only_useless = false;
+                            self.only_degenerate = false;
+                            self.apply_filter_and_sort();
+                        }
+                    }
+                });
+
+                // Extension / size filter settings
+                if self.show_filter_settings {
+                    ui.add_space(8.0);
+                    ui.separator();
+                    egui::Grid::new("filter_settings").num_columns(2).show(ui, |ui| {
+                        ui.label("Include extensions:");
+                        if ui.text_edit_singleline(&mut self.allowed_ext_input).changed() {
+                            self.allowed_extensions = parse_extension_list(&self.allowed_ext_input);
+                            self.apply_filter_and_sort();
+                        }
+                        ui.end_row();
+
+                        ui.label("Exclude extensions:");
+                        if ui.text_edit_singleline(&mut self.excluded_ext_input).changed() {
+                            self.excluded_extensions = parse_extension_list(&self.excluded_ext_input);
+                            self.apply_filter_and_sort();
+                        }
+                        ui.end_row();
+
+                        ui.label("Min size (MB):");
+                        if ui.text_edit_singleline(&mut self.min_size_mb_input).changed() {
+                            let mb: f64 = self.min_size_mb_input.trim().parse().unwrap_or(0.0);
+                            self.min_size_filter = (mb.max(0.0) * 1_048_576.0) as u64;
+                            self.apply_filter_and_sort();
+                        }
+                        ui.end_row();
+
+                        ui.label("Exclude paths:");
+                        // Path substrings are a scan-time filter, so a change
+                        // has to re-read the directory rather than re-filter.
+                        let path_resp = ui.text_edit_singleline(&mut self.excluded_paths_input);
+                        if path_resp.lost_focus() && path_resp.changed() {
+                            self.excluded_paths = self
+                                .excluded_paths_input
+                                .split(',')
+                                .map(|s| s.trim().to_string())
+                                .filter(|s| !s.is_empty())
+                                .collect();
+                            if let Some(path) = self.current_path.clone() {
+                                self.load_directory(&path);
+                            }
+                        }
+                        ui.end_row();
+
+                        ui.label("Exclude globs:");
+                        // ncdu-style patterns (*/node_modules, *.iso, $*) skipped
+                        // during the walk; a change re-reads so sizes recompute.
+                        let glob_resp = ui.text_edit_singleline(&mut self.exclude_globs_input);
+                        if glob_resp.lost_focus() && glob_resp.changed() {
+                            self.exclude_globs = self
+                                .exclude_globs_input
+                                .split(',')
+                                .map(|s| s.trim().to_string())
+                                .filter(|s| !s.is_empty())
+                                .collect();
+                            save_exclude_globs(&self.exclude_globs);
+                            if let Some(path) = self.current_path.clone() {
+                                self.load_directory(&path);
+                            }
+                        }
+                        ui.end_row();
+
+                        ui.label("Hide excluded:");
+                        if ui.checkbox(&mut self.hide_excluded, "").changed() {
+                            self.apply_filter_and_sort();
+                        }
+                        ui.end_row();
+
+                        ui.label("Scan worker threads:");
+                        ui.add(egui::Slider::new(&mut self.size_worker_threads, 1..=32));
+                        ui.end_row();
+
+                        ui.label("Delete to Recycle Bin:");
+                        // Default delete behavior; persisted so it survives restarts.
+                        if ui.checkbox(&mut self.delete_to_trash, "").changed() {
+                            save_delete_to_trash(self.delete_to_trash);
+                        }
+                        ui.end_row();
+
+                        ui.label("Natural name sort:");
+                        // Digit-aware ordering (file2 before file10) when ordering by name.
+                        if ui.checkbox(&mut self.natural_sort, "").changed()
+                            && self.sort_column == SortColumn::Name {
+                            self.sort_file_items();
+                        }
+                        ui.end_row();
+
+                        ui.label("Thumbnail previews:");
+                        // Decode real previews for photos/videos instead of glyphs;
+                        // clearing frees the cached textures immediately.
+                        if ui.checkbox(&mut self.show_thumbnails, "").changed()
+                            && !self.show_thumbnails {
+                            self.thumbnails.clear();
+                        }
+                        ui.end_row();
+                    });
+                    if ui.button("‚ö° Useless-files preset (logs/tmp/cache)").clicked() {
+                        self.apply_useless_preset();
+                    }
+                }
+            });
+        
         ui.add_space(10.0);
 
         // File list with header inside ScrollArea for consistent width
@@ -1200,7 +2927,8 @@ impl DiskDashboard {
                                     egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
                                     |ui| {
                                         let arrow = if self.sort_column == SortColumn::Size && self.sort_direction == SortDirection::Ascending { "‚ñ≤" } else if self.sort_column == SortColumn::Size { "‚ñº" } else { "" };
-                                        if ui.selectable_label(self.sort_column == SortColumn::Size, format!("Size {}", arrow)).clicked() {
+                                        let size_hdr = if self.size_mode == SizeMode::DiskUsage { "Size (disk)" } else { "Size" };
+                                        if ui.selectable_label(self.sort_column == SortColumn::Size, format!("{} {}", size_hdr, arrow)).clicked() {
                                             if self.sort_column == SortColumn::Size {
                                                 self.sort_direction = match self.sort_direction {
                                                     SortDirection::Ascending => SortDirection::Descending,
@@ -1227,66 +2955,166 @@ impl DiskDashboard {
                         }
                     }
 
+                    // Bulk-selection helpers operating over the filtered view.
+                    ui.menu_button("‚òë Select‚ñæ", |ui| {
+                        if ui.button("Select all").clicked() {
+                            for item in &self.filtered_items {
+                                self.selected_items.insert(item.path.clone());
+                            }
+                            ui.close_menu();
+                        }
+                        if ui.button("Invert selection").clicked() {
+                            for item in self.filtered_items.clone() {
+                                if self.selected_items.contains(&item.path) {
+                                    self.selected_items.remove(&item.path);
+                                } else {
+                                    self.selected_items.insert(item.path.clone());
+                                }
+                            }
+                            ui.close_menu();
+                        }
+                        ui.separator();
+                        if ui.button("Select all Useless").clicked() {
+                            for item in &self.filtered_items {
+                                if item.category == FileCategory::Useless {
+                                    self.selected_items.insert(item.path.clone());
+                                }
+                            }
+                            ui.close_menu();
+                        }
+                        if ui.button("Reclaim empty files & folders").clicked() {
+                            // Bottom-up sweep of the whole subtree, not just the
+                            // filtered view, so nested empties are caught too.
+                            let mut n = 0;
+                            for path in find_empty_reclaimable(current_path) {
+                                if self.selected_items.insert(path) {
+                                    n += 1;
+                                }
+                            }
+                            self.toast_message = Some((format!("Selected {} empty items", n), 2.5));
+                            ui.close_menu();
+                        }
+                        if ui.button("Select duplicate copies").clicked() {
+                            // Hash the current view for byte-identical files and
+                            // select every copy but the first in each group.
+                            let groups = find_duplicates(&self.filtered_items);
+                            let mut n = 0;
+                            for path in duplicate_extra_copies(&groups) {
+                                if self.selected_items.insert(path) {
+                                    n += 1;
+                                }
+                            }
+                            self.toast_message = Some((format!("Selected {} duplicate copies", n), 2.5));
+                            ui.close_menu();
+                        }
+                        ui.horizontal(|ui| {
+                            ui.label("Usefulness <");
+                            ui.add(egui::Slider::new(&mut self.select_usefulness_below, 0.0..=100.0));
+                            if ui.button("Go").clicked() {
+                                let cutoff = self.select_usefulness_below;
+                                for item in &self.filtered_items {
+                                    if !item.is_dir && item.usefulness < cutoff {
+                                        self.selected_items.insert(item.path.clone());
+                                    }
+                                }
+                                ui.close_menu();
+                            }
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Older than (days)");
+                            ui.add(egui::Slider::new(&mut self.select_older_than_days, 0..=3650));
+                            if ui.button("Go").clicked() {
+                                let cutoff = SystemTime::now()
+                                    .checked_sub(std::time::Duration::from_secs(
+                                        self.select_older_than_days as u64 * 86_400));
+                                if let Some(cutoff) = cutoff {
+                                    for item in &self.filtered_items {
+                                        if item.modified.map(|m| m < cutoff).unwrap_or(false) {
+                                            self.selected_items.insert(item.path.clone());
+                                        }
+                                    }
+                                }
+                                ui.close_menu();
+                            }
+                        });
+                        if !self.duplicate_groups.is_empty() {
+                            ui.separator();
+                            if ui.button("Keep one newest per duplicate group").clicked() {
+                                for path in duplicate_stale_copies(&self.duplicate_groups) {
+                                    self.selected_items.insert(path);
+                                }
+                                ui.close_menu();
+                            }
+                        }
+                    });
+
                     // Show selection count and delete button when items selected
                     if !self.selected_items.is_empty() {
                         ui.separator();
                         ui.label(egui::RichText::new(format!("üìã {} selected", self.selected_items.len()))
                             .color(egui::Color32::from_rgb(100, 180, 255)));
 
-                        if ui.add(egui::Button::new("üóëÔ∏è Delete Selected")
-                            .fill(egui::Color32::from_rgb(150, 50, 50)))
-                            .clicked()
+                        // Delete every selected item using the default mode; protected
+                        // paths are skipped inside delete_paths. When trashing, the toast
+                        // it raises offers an Undo.
+                        let (del_label, del_fill) = if self.delete_to_trash {
+                            ("üóëÔ∏è Delete Selected", egui::Color32::from_rgb(150, 50, 50))
+                        } else {
+                            ("‚ö†Ô∏è Delete Selected (permanent)", egui::Color32::from_rgb(150, 50, 50))
+                        };
+                        if ui.add(egui::Button::new(del_label).fill(del_fill)).clicked() {
+                            let to_trash = self.delete_to_trash;
+                            self.delete_paths(self.selected_items.clone().into_iter().collect(), to_trash);
+                        }
+                        // Secondary, always-permanent option kept one explicit click away
+                        // whenever trashing is the default.
+                        if self.delete_to_trash
+                            && ui.add(egui::Button::new("‚ö†Ô∏è Permanently delete")
+                                .fill(egui::Color32::from_rgb(90, 30, 30)))
+                                .clicked()
                         {
-                            // Delete all selected items (skip protected)
-                            let mut deleted = 0;
-                            let mut skipped = 0;
-                            let mut errors = Vec::new();
-                            for path in self.selected_items.clone() {
-                                // Check if protected
-                                let path_lower = path.to_string_lossy().to_lowercase();
-                                let name_lower = path.file_name()
-                                    .map(|n| n.to_string_lossy().to_lowercase())
-                                    .unwrap_or_default();
-                                let is_protected = name_lower.starts_with("$") ||
-                                    name_lower == "system volume information" ||
-                                    name_lower == "recovery" ||
-                                    name_lower == "boot" ||
-                                    path_lower.contains("\\windows\\") ||
-                                    path_lower.ends_with("\\windows") ||
-                                    path_lower.contains("program files");
-
-                                if is_protected {
-                                    skipped += 1;
-                                    continue;
-                                }
+                            self.delete_paths(self.selected_items.clone().into_iter().collect(), false);
+                        }
 
-                                let result = if path.is_dir() {
-                                    fs::remove_dir_all(&path)
-                                } else {
-                                    fs::remove_file(&path)
-                                };
-                                match result {
-                                    Ok(_) => {
-                                        deleted += 1;
-                                        // Invalidate cache for ancestors
-                                        let mut ancestor = path.parent();
-                                        while let Some(parent) = ancestor {
-                                            self.folder_size_cache.remove(parent);
-                                            self.pending_size_calculations.remove(parent);
-                                            ancestor = parent.parent();
+                        if ui.add(egui::Button::new("üìÅ Move to‚Ä¶")
+                            .fill(egui::Color32::from_rgb(40, 60, 90)))
+                            .clicked()
+                        {
+                            // Pick a destination folder, then move every selected entry there.
+                            if let Some(dest) = rfd::FileDialog::new().pick_folder() {
+                                let mut moved = 0;
+                                let mut errors = Vec::new();
+                                for path in self.selected_items.clone() {
+                                    let file_name = path.file_name()
+                                        .map(|n| n.to_os_string())
+                                        .unwrap_or_default();
+                                    let target = dest.join(&file_name);
+                                    match move_path(&path, &target) {
+                                        Ok(_) => {
+                                            moved += 1;
+                                            // Invalidate cached sizes for both ancestor chains.
+                                            for start in [path.parent(), Some(dest.as_path())] {
+                                                let mut ancestor = start;
+                                                while let Some(parent) = ancestor {
+                                                    self.folder_size_cache.remove(parent);
+                                                    self.pending_size_calculations.remove(parent);
+                                                    ancestor = parent.parent();
+                                                }
+                                            }
                                         }
+                                        Err(e) => errors.push(format!("{}: {}",
+                                            path.file_name().unwrap_or_default().to_string_lossy(), e)),
                                     }
-                                    Err(e) => errors.push(format!("{}: {}", path.file_name().unwrap_or_default().to_string_lossy(), e)),
                                 }
-                            }
-                            self.selected_items.clear();
-                            self.needs_refresh = true;
-                            if errors.is_empty() && skipped == 0 {
-                                self.toast_message = Some((format!("üóëÔ∏è Deleted {} items", deleted), 2.0));
-                            } else if skipped > 0 {
-                                self.toast_message = Some((format!("üîí Skipped {} protected, deleted {}", skipped, deleted), 3.0));
-                            } else {
-                                self.toast_message = Some((format!("‚ö†Ô∏è Deleted {} items, {} failed", deleted, errors.len()), 3.0));
+                                self.selected_items.clear();
+                                self.needs_refresh = true;
+                                if errors.is_empty() {
+                                    self.toast_message = Some((format!("üìÅ Moved {} items", moved), 2.0));
+                                } else {
+                                    self.delete_error = Some(format!(
+                                        "Moved {} items, {} could not be moved:\n{}",
+                                        moved, errors.len(), errors.join("\n")));
+                                }
                             }
                         }
 
@@ -1329,6 +3157,12 @@ impl DiskDashboard {
             FileCategory::System => "System",
             FileCategory::Regular => "Regular",
             FileCategory::Useless => "Useless",
+            FileCategory::Duplicate => "Duplicate",
+            FileCategory::Suspicious => "Suspicious",
+            FileCategory::SimilarImage => "Similar Image",
+            FileCategory::Empty => "Empty",
+            FileCategory::EmptyFolder => "Empty Folder",
+            FileCategory::BrokenSymlink => "Broken Symlink",
             FileCategory::Unknown => "Unknown",
         };
 
@@ -1337,6 +3171,12 @@ impl DiskDashboard {
             FileCategory::System => egui::Color32::from_rgb(170, 85, 255),    // Neon purple
             FileCategory::Regular => egui::Color32::from_rgb(0, 212, 255),    // Electric blue
             FileCategory::Useless => egui::Color32::from_rgb(255, 51, 102),   // Neon red
+            FileCategory::Duplicate => egui::Color32::from_rgb(255, 170, 0),  // Neon amber
+            FileCategory::Suspicious => egui::Color32::from_rgb(255, 110, 0), // Neon orange
+            FileCategory::SimilarImage => egui::Color32::from_rgb(255, 0, 200), // Neon magenta
+            FileCategory::Empty => egui::Color32::from_rgb(120, 120, 140),    // Dim grey
+            FileCategory::EmptyFolder => egui::Color32::from_rgb(120, 120, 140), // Dim grey
+            FileCategory::BrokenSymlink => egui::Color32::from_rgb(255, 51, 102), // Neon red
             FileCategory::Unknown => egui::Color32::from_rgb(100, 80, 140),   // Dim purple
         };
 
@@ -1364,7 +3204,8 @@ impl DiskDashboard {
                 }
             }
         } else {
-            format_size(item.size) // Show file size
+            // Report apparent or on-disk bytes per the active size mode.
+            format_size(display_size(&item.path, item.size, self.size_mode))
         };
 
         let is_empty_folder = item.is_dir && item.child_count == Some(0);
@@ -1465,12 +3306,38 @@ impl DiskDashboard {
                                 FileCategory::System => "‚öôÔ∏è",
                                 FileCategory::Regular => "üìÑ",
                                 FileCategory::Useless => "üóëÔ∏è",
+                                FileCategory::Duplicate => "DUP",
+                                FileCategory::Suspicious => "‚ö†Ô∏è",
+                                FileCategory::SimilarImage => "üñº",
+                                FileCategory::Empty => "‚óã",
+                                FileCategory::EmptyFolder => "üìÇ",
+                                FileCategory::BrokenSymlink => "üîó",
                                 FileCategory::Unknown => "‚ùì",
                             }
                         }
                     };
 
-                    ui.label(egui::RichText::new(icon_text).size(icon_size));
+                    // Draw a real preview for photos/videos when available,
+                    // otherwise fall back to the glyph for this row.
+                    match icon_source(
+                        ui.ctx(),
+                        &mut self.thumbnails,
+                        &item.path,
+                        item.is_dir,
+                        item.category,
+                        self.show_thumbnails,
+                    ) {
+                        IconSource::Thumbnail(tex) => {
+                            let sized = egui::load::SizedTexture::new(
+                                tex.id(),
+                                egui::vec2(icon_size, icon_size),
+                            );
+                            ui.add(egui::Image::new(sized));
+                        }
+                        IconSource::Theme(_) | IconSource::Glyph(_) => {
+                            ui.label(egui::RichText::new(icon_text).size(icon_size));
+                        }
+                    }
                     ui.add_space(12.0);
                     
                     // Name column - neon colors for cyberpunk theme
@@ -1483,6 +3350,12 @@ impl DiskDashboard {
                     } else {
                         egui::Color32::from_rgb(200, 180, 255)     // Light purple for files
                     };
+                    // Dim excluded items so they read as "present but ignored".
+                    let name_color = if item.excluded {
+                        name_color.gamma_multiply(0.4)
+                    } else {
+                        name_color
+                    };
 
                     // Use regular label instead of selectable_label to avoid conflicting hover styles
                     let name_response = ui.add(
@@ -1492,6 +3365,17 @@ impl DiskDashboard {
                         .sense(egui::Sense::click())
                     );
 
+                    // For media files, kick off (or reuse) an ffprobe probe and
+                    // surface resolution/duration/codec on hover once it lands.
+                    if !item.is_dir && is_media_file(&item.name) {
+                        if let Some(info) = self.request_media_info(&item.path, item.modified) {
+                            let tip = format_media_tooltip(&info);
+                            if !tip.is_empty() {
+                                name_response.clone().on_hover_text(tip);
+                            }
+                        }
+                    }
+
                     // Name click - navigate for folders, open for files
                     if name_response.clicked() {
                         if item.is_dir {
@@ -1543,6 +3427,12 @@ impl DiskDashboard {
                             ui.close_menu();
                         }
 
+                        // Pin folders to the sidebar bookmark list.
+                        if item_is_dir && ui.button("⭐ Add to bookmarks").clicked() {
+                            self.add_bookmark(item_path.clone());
+                            ui.close_menu();
+                        }
+
                         // Only show delete option for non-protected items
                         let item_category = item.category;
                         if item_category != FileCategory::MustKeep && item_category != FileCategory::System {
@@ -1732,7 +3622,8 @@ impl DiskDashboard {
                 ui.separator();
                 ui.label(format!("Path: {}", item.path.to_string_lossy()));
                 if !item.is_dir {
-                    ui.label(format!("Size: {}", format_size(item.size)));
+                    ui.label(format!("Size ({}): {}", self.size_mode.label(),
+                        format_size(display_size(&item.path, item.size, self.size_mode))));
                 }
                 ui.label(format!("Category: {} {}", category_text,
                     if item.category == FileCategory::MustKeep { "üîí" }
@@ -1917,6 +3808,1373 @@ fn format_size(bytes: u64) -> String {
     }
 }
 
+/// Cluster size assumed for the apparent→on-disk rounding fallback when the
+/// real volume geometry is unavailable. 4 KiB is the NTFS/ext4 default.
+const DEFAULT_CLUSTER_SIZE: u64 = 4096;
+
+/// Round an apparent byte count up to whole clusters — the fallback estimate of
+/// on-disk usage when a platform-specific allocated size can't be obtained.
+fn round_up_to_cluster(len: u64, cluster: u64) -> u64 {
+    if cluster == 0 {
+        return len;
+    }
+    len.div_ceil(cluster) * cluster
+}
+
+/// Bytes a file actually occupies on disk.
+///
+/// On Unix this is `blocks * 512` from the inode. On other platforms we round
+/// the apparent length up to the default cluster size, which captures the
+/// slack small files consume without a per-volume query.
+fn on_disk_size(metadata: &fs::Metadata) -> u64 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        return metadata.blocks() * 512;
+    }
+    #[allow(unreachable_code)]
+    {
+        round_up_to_cluster(metadata.len(), DEFAULT_CLUSTER_SIZE)
+    }
+}
+
+/// Size of `path` under the chosen [`SizeMode`]; apparent mode (and any stat
+/// failure) falls back to the already-computed `apparent` value.
+fn display_size(path: &Path, apparent: u64, mode: SizeMode) -> u64 {
+    match mode {
+        SizeMode::Apparent => apparent,
+        SizeMode::DiskUsage => fs::metadata(path)
+            .map(|m| on_disk_size(&m))
+            .unwrap_or(apparent),
+    }
+}
+
+/// Produce a user-facing explanation for a failed delete/trash operation.
+///
+/// On Windows, a sharing violation means another process holds the file open;
+/// the Restart Manager can name those processes, turning an opaque error into
+/// actionable information. Everywhere else we fall back to the raw message.
+fn describe_delete_error(path: &Path, raw: &str) -> String {
+    #[cfg(windows)]
+    {
+        let holders = locking_processes(path);
+        if !holders.is_empty() {
+            let who = holders
+                .iter()
+                .map(|(name, pid)| format!("{} ({})", name, pid))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return format!("Failed to delete: {}\nLocked by: {}", raw, who);
+        }
+    }
+    let _ = path;
+    format!("Failed to delete: {}", raw)
+}
+
+/// Query the Windows Restart Manager for the processes holding `path` open.
+///
+/// Returns `(application name, pid)` pairs, or an empty vec if the lookup
+/// fails or nothing holds the file.
+#[cfg(windows)]
+fn locking_processes(path: &Path) -> Vec<(String, u32)> {
+    use windows::core::PCWSTR;
+    use windows::Win32::System::RestartManager::{
+        RmEndSession, RmGetList, RmRegisterResources, RmStartSession, RM_PROCESS_INFO,
+        RM_REBOOT_REASON_NONE,
+    };
+
+    let mut session: u32 = 0;
+    let mut key = [0u16; 64]; // CCH_RM_SESSION_KEY + 1
+    // SAFETY: FFI into the Restart Manager; all buffers are sized per the API docs.
+    unsafe {
+        if RmStartSession(&mut session, 0, windows::core::PWSTR(key.as_mut_ptr())).is_err() {
+            return Vec::new();
+        }
+        let wide: Vec<u16> = path
+            .as_os_str()
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect();
+        let resources = [PCWSTR(wide.as_ptr())];
+        if RmRegisterResources(session, Some(&resources), None, None).is_err() {
+            let _ = RmEndSession(session);
+            return Vec::new();
+        }
+
+        let mut needed: u32 = 0;
+        let mut count: u32 = 0;
+        let mut reason = RM_REBOOT_REASON_NONE;
+        // First call with a zero-length buffer to learn how many entries exist.
+        let _ = RmGetList(session, &mut needed, &mut count, None, &mut reason.0);
+        let mut infos = vec![RM_PROCESS_INFO::default(); needed.max(1) as usize];
+        count = infos.len() as u32;
+        let result = RmGetList(session, &mut needed, &mut count, Some(infos.as_mut_ptr()), &mut reason.0);
+        let mut out = Vec::new();
+        if result.is_ok() {
+            for info in infos.iter().take(count as usize) {
+                let name = String::from_utf16_lossy(&info.strAppName)
+                    .trim_end_matches('\0')
+                    .to_string();
+                out.push((name, info.Process.dwProcessId));
+            }
+        }
+        let _ = RmEndSession(session);
+        out
+    }
+}
+
+#[cfg(windows)]
+use std::os::windows::ffi::OsStrExt;
+
+/// Parse a comma-separated extension list into a normalised (lowercase, no dot) set.
+fn parse_extension_list(input: &str) -> HashSet<String> {
+    input
+        .split(',')
+        .map(|s| s.trim().trim_start_matches('.').to_lowercase())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Compare two names in natural (human) order so `file2` sorts before `file10`.
+///
+/// Each name is split into alternating non-digit and digit runs. Non-digit runs
+/// compare case-insensitively; digit runs compare by numeric value (leading
+/// zeros ignored), falling back to run length and then raw lexical order when
+/// the numeric values tie. Shorter names lose when one is a prefix of the other.
+fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    let mut ai = a.chars().peekable();
+    let mut bi = b.chars().peekable();
+
+    loop {
+        match (ai.peek().copied(), bi.peek().copied()) {
+            (None, None) => return std::cmp::Ordering::Equal,
+            (None, Some(_)) => return std::cmp::Ordering::Less,
+            (Some(_), None) => return std::cmp::Ordering::Greater,
+            (Some(ca), Some(cb)) => {
+                if ca.is_ascii_digit() && cb.is_ascii_digit() {
+                    let ra = take_digit_run(&mut ai);
+                    let rb = take_digit_run(&mut bi);
+                    let na = ra.trim_start_matches('0');
+                    let nb = rb.trim_start_matches('0');
+                    // Longer digit string (sans leading zeros) is the larger number.
+                    let ord = na.len().cmp(&nb.len())
+                        .then_with(|| na.cmp(nb))
+                        // Ties on value: the shorter (less zero-padded) run sorts first, then lexical.
+                        .then_with(|| ra.len().cmp(&rb.len()))
+                        .then_with(|| ra.cmp(&rb));
+                    if ord != std::cmp::Ordering::Equal {
+                        return ord;
+                    }
+                } else {
+                    let la = ca.to_ascii_lowercase();
+                    let lb = cb.to_ascii_lowercase();
+                    if la != lb {
+                        return la.cmp(&lb);
+                    }
+                    ai.next();
+                    bi.next();
+                }
+            }
+        }
+    }
+}
+
+/// Consume and return the leading run of ASCII digits from `iter`.
+fn take_digit_run(iter: &mut std::iter::Peekable<std::str::Chars<'_>>) -> String {
+    let mut run = String::new();
+    while let Some(&c) = iter.peek() {
+        if c.is_ascii_digit() {
+            run.push(c);
+            iter.next();
+        } else {
+            break;
+        }
+    }
+    run
+}
+
+/// Lowercase extension of `name_or_path` without the leading dot, or an empty
+/// string when there is none. Shared by [`categorize_file`] and the filter spec
+/// so both agree on what an entry's "extension" is.
+fn lowercase_extension(name_or_path: &str) -> String {
+    Path::new(name_or_path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default()
+}
+
+/// Match `text` against a glob `pattern` supporting `*` (any run within a path
+/// segment), `?` (single char), and `**` (any run including separators).
+///
+/// Matching is case-insensitive and operates on the raw byte-ish char stream;
+/// callers decide whether to feed a full path or just a basename.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.to_lowercase().chars().collect();
+    let t: Vec<char> = text.to_lowercase().chars().collect();
+    glob_match_inner(&p, &t)
+}
+
+fn glob_match_inner(p: &[char], t: &[char]) -> bool {
+    // Index-based backtracking matcher.
+    let (mut pi, mut ti) = (0usize, 0usize);
+    let (mut star_p, mut star_t): (Option<usize>, usize) = (None, 0);
+    let mut double_star = false;
+    while ti < t.len() {
+        if pi < p.len() && p[pi] == '*' {
+            // Collapse a run of '*'; remember whether it was '**'.
+            double_star = pi + 1 < p.len() && p[pi + 1] == '*';
+            while pi < p.len() && p[pi] == '*' {
+                pi += 1;
+            }
+            star_p = Some(pi);
+            star_t = ti;
+            continue;
+        }
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+            continue;
+        }
+        if let Some(sp) = star_p {
+            // A single '*' does not cross path separators; '**' may.
+            if !double_star && t[star_t] == '/' {
+                return false;
+            }
+            pi = sp;
+            star_t += 1;
+            ti = star_t;
+            continue;
+        }
+        return false;
+    }
+    while pi < p.len() && p[pi] == '*' {
+        pi += 1;
+    }
+    pi == p.len()
+}
+
+/// Whether `path` is excluded by any of the compiled glob `patterns`, testing
+/// both the full path and the basename so `*.iso` and `*/node_modules` both work.
+fn path_excluded(patterns: &[String], path: &Path) -> bool {
+    if patterns.is_empty() {
+        return false;
+    }
+    let full = path.to_string_lossy().replace('\\', "/");
+    let base = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    patterns.iter().any(|pat| {
+        let pat = pat.trim();
+        !pat.is_empty() && (glob_match(pat, &full) || glob_match(pat, &base))
+    })
+}
+
+/// Decide whether a directory entry should be skipped at scan time.
+///
+/// Excluded-path substrings remove any matching entry (directories included),
+/// while the extension whitelist/blacklist only applies to files so that
+/// directories remain navigable.
+fn should_skip_scan_entry(
+    path: &Path,
+    name: &str,
+    is_dir: bool,
+    allowed: &HashSet<String>,
+    excluded_exts: &HashSet<String>,
+    excluded_paths: &[String],
+) -> bool {
+    let path_lower = path.to_string_lossy().to_lowercase();
+    if excluded_paths.iter().any(|p| {
+        let p = p.to_lowercase();
+        !p.is_empty() && path_lower.contains(&p)
+    }) {
+        return true;
+    }
+    if is_dir {
+        return false;
+    }
+    let ext = Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if excluded_exts.contains(&ext) {
+        return true;
+    }
+    if !allowed.is_empty() && !allowed.contains(&ext) {
+        return true;
+    }
+    false
+}
+
+/// Decide whether an item survives the extension/size filter chain.
+///
+/// Directories are always kept (they are needed for navigation). For files, a
+/// non-empty `allowed` set acts as a whitelist, `excluded` always removes
+/// matches, and `min_size` drops anything smaller than the threshold.
+fn passes_extension_filter(
+    name: &str,
+    is_dir: bool,
+    size: u64,
+    allowed: &HashSet<String>,
+    excluded: &HashSet<String>,
+    min_size: u64,
+) -> bool {
+    if is_dir {
+        return true;
+    }
+    if size < min_size {
+        return false;
+    }
+    let ext = Path::new(name)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if excluded.contains(&ext) {
+        return false;
+    }
+    if !allowed.is_empty() && !allowed.contains(&ext) {
+        return false;
+    }
+    true
+}
+
+/// Parse the `ffprobe -print_format json -show_format -show_streams` payload
+/// into a [`MediaInfo`], pulling the first video/audio stream's details.
+///
+/// Missing or malformed fields degrade to `None`/defaults rather than failing,
+/// so a partial probe still surfaces whatever ffprobe managed to report.
+fn parse_ffprobe_json(json: &str) -> Option<MediaInfo> {
+    let root: serde_json::Value = serde_json::from_str(json).ok()?;
+    let mut info = MediaInfo::default();
+
+    if let Some(format) = root.get("format") {
+        if let Some(name) = format.get("format_long_name").and_then(|v| v.as_str()) {
+            info.format = name.to_string();
+        }
+        if let Some(dur) = format.get("duration").and_then(|v| v.as_str()) {
+            info.duration = dur.parse().unwrap_or(0.0);
+        }
+    }
+
+    if let Some(streams) = root.get("streams").and_then(|v| v.as_array()) {
+        for stream in streams {
+            if let Some(codec) = stream.get("codec_long_name").and_then(|v| v.as_str()) {
+                info.codecs.push(codec.to_string());
+            }
+            match stream.get("codec_type").and_then(|v| v.as_str()) {
+                Some("video") if info.width.is_none() => {
+                    info.width = stream.get("width").and_then(|v| v.as_u64()).map(|w| w as u32);
+                    info.height = stream.get("height").and_then(|v| v.as_u64()).map(|h| h as u32);
+                    info.framerate = stream
+                        .get("avg_frame_rate")
+                        .and_then(|v| v.as_str())
+                        .and_then(parse_frame_rate);
+                }
+                Some("audio") if info.channels.is_none() => {
+                    info.channels = stream.get("channels").and_then(|v| v.as_u64()).map(|c| c as u32);
+                    info.sample_rate = stream
+                        .get("sample_rate")
+                        .and_then(|v| v.as_str())
+                        .and_then(|s| s.parse().ok());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Some(info)
+}
+
+/// Evaluate ffprobe's `num/den` framerate strings (e.g. `"30000/1001"`).
+fn parse_frame_rate(raw: &str) -> Option<f64> {
+    let (num, den) = raw.split_once('/')?;
+    let num: f64 = num.parse().ok()?;
+    let den: f64 = den.parse().ok()?;
+    if den == 0.0 {
+        None
+    } else {
+        Some(num / den)
+    }
+}
+
+/// Render a [`MediaInfo`] as a multi-line hover tooltip, omitting fields the
+/// probe couldn't fill.
+fn format_media_tooltip(info: &MediaInfo) -> String {
+    let mut lines = Vec::new();
+    if !info.format.is_empty() {
+        lines.push(format!("Format: {}", info.format));
+    }
+    if info.duration > 0.0 {
+        let total = info.duration as u64;
+        lines.push(format!("Duration: {}:{:02}", total / 60, total % 60));
+    }
+    if let (Some(w), Some(h)) = (info.width, info.height) {
+        match info.framerate {
+            Some(fps) => lines.push(format!("Resolution: {}x{} @ {:.0} fps", w, h, fps)),
+            None => lines.push(format!("Resolution: {}x{}", w, h)),
+        }
+    }
+    if let Some(ch) = info.channels {
+        match info.sample_rate {
+            Some(sr) => lines.push(format!("Audio: {} ch @ {} Hz", ch, sr)),
+            None => lines.push(format!("Audio: {} ch", ch)),
+        }
+    }
+    if !info.codecs.is_empty() {
+        lines.push(format!("Codecs: {}", info.codecs.join(", ")));
+    }
+    lines.join("\n")
+}
+
+/// Refine a media file's usefulness score using its probed metadata: long,
+/// high-resolution videos are likelier to be keepers than short low-res clips.
+fn refine_media_usefulness(base: f32, info: &MediaInfo) -> f32 {
+    let mut score = base;
+    let long = info.duration >= 20.0 * 60.0;
+    let high_res = info.height.map(|h| h >= 720).unwrap_or(false);
+    if long && high_res {
+        score = (score + 10.0).min(95.0);
+    } else if info.duration > 0.0 && info.duration < 30.0 && !high_res {
+        score = (score - 15.0).max(5.0);
+    }
+    score
+}
+
+/// Build an [`FsInfo`] from a `sysinfo` disk handle, normalising the fields the
+/// disk cards and the Filesystems view care about.
+fn fs_info_from_disk(disk: &sysinfo::Disk) -> FsInfo {
+    let fs_type = disk.file_system().to_string_lossy().to_string();
+    FsInfo {
+        network: is_network_fs(&fs_type),
+        fs_type,
+        device: disk.name().to_string_lossy().to_string(),
+        removable: disk.is_removable(),
+        read_only: disk.is_read_only(),
+    }
+}
+
+/// Neon usage color shared by the disk cards, pie legend and Filesystems table:
+/// red past 90% full, orange past 75%, green otherwise.
+fn usage_color(percent: f64) -> egui::Color32 {
+    if percent > 90.0 {
+        egui::Color32::from_rgb(255, 51, 102) // Neon red
+    } else if percent > 75.0 {
+        egui::Color32::from_rgb(255, 136, 0) // Neon orange
+    } else {
+        egui::Color32::from_rgb(0, 255, 136) // Neon green
+    }
+}
+
+/// Whether a filesystem type name denotes a network mount.
+fn is_network_fs(fs_type: &str) -> bool {
+    let t = fs_type.to_lowercase();
+    matches!(t.as_str(), "nfs" | "nfs4" | "cifs" | "smbfs" | "smb" | "afpfs" | "sshfs" | "fuse.sshfs" | "webdav")
+}
+
+/// Reclaimable bytes for a duplicate group: every copy beyond the first is waste.
+fn reclaimable_bytes(size: u64, count: usize) -> u64 {
+    size.saturating_mul(count.saturating_sub(1) as u64)
+}
+
+/// Hash the first `limit` bytes of a file (a cheap prefilter before full hashing).
+fn hash_file_prefix(path: &Path, limit: usize) -> std::io::Result<u64> {
+    use std::hash::Hasher;
+    use std::io::Read;
+    let mut file = fs::File::open(path)?;
+    let mut buf = vec![0u8; limit];
+    let n = file.read(&mut buf)?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    hasher.write(&buf[..n]);
+    Ok(hasher.finish())
+}
+
+/// Hash the full contents of a file by streaming it in fixed-size chunks.
+fn hash_file_full(path: &Path) -> std::io::Result<u64> {
+    use std::hash::Hasher;
+    use std::io::Read;
+    let mut file = fs::File::open(path)?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let mut buf = [0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.write(&buf[..n]);
+    }
+    Ok(hasher.finish())
+}
+
+/// Compute a 64-bit difference hash (dHash) from a 9×8 grayscale pixel grid.
+///
+/// Pixels are row-major, 9 wide by 8 tall. For each of the 8 rows we emit one
+/// bit per adjacent pair (8 pairs/row), set when the left pixel is brighter
+/// than the right, yielding 8×8 = 64 bits.
+fn compute_dhash(gray_9x8: &[u8]) -> u64 {
+    let (w, h) = (9usize, 8usize);
+    debug_assert_eq!(gray_9x8.len(), w * h);
+    let mut hash = 0u64;
+    let mut bit = 0;
+    for row in 0..h {
+        for col in 0..w - 1 {
+            let left = gray_9x8[row * w + col];
+            let right = gray_9x8[row * w + col + 1];
+            if left > right {
+                hash |= 1 << bit;
+            }
+            bit += 1;
+        }
+    }
+    hash
+}
+
+/// Hamming distance between two 64-bit perceptual hashes.
+fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+/// Express a 64-bit Hamming distance as a 0–100% similarity, so a distance of 0
+/// reads as an exact visual match and 64 as wholly unrelated.
+fn hamming_similarity_percent(distance: u32) -> f32 {
+    (1.0 - distance as f32 / 64.0) * 100.0
+}
+
+/// Similarity of a cluster of images, taken from the *widest* pairwise distance
+/// within it (the two least-alike members), so the score is a lower bound on how
+/// alike everything in the group is. Returns 100% for groups of fewer than two.
+fn group_similarity_percent(paths: &[PathBuf]) -> f32 {
+    let hashes: Vec<u64> = paths.iter().filter_map(|p| dhash_image_cached(p)).collect();
+    let mut worst = 0;
+    for i in 0..hashes.len() {
+        for j in i + 1..hashes.len() {
+            worst = worst.max(hamming_distance(hashes[i], hashes[j]));
+        }
+    }
+    hamming_similarity_percent(worst)
+}
+
+/// Greedily cluster hashes whose Hamming distance to a group's first member is
+/// at most `threshold`.
+///
+/// Returns only groups with at least two members. Simple O(N²) scan; callers
+/// working on very large libraries should pre-bucket by top bits first.
+fn cluster_by_hamming(hashes: &[(PathBuf, u64)], threshold: u32) -> Vec<Vec<PathBuf>> {
+    let mut assigned = vec![false; hashes.len()];
+    let mut clusters = Vec::new();
+    for i in 0..hashes.len() {
+        if assigned[i] {
+            continue;
+        }
+        let mut group = vec![hashes[i].0.clone()];
+        assigned[i] = true;
+        for j in i + 1..hashes.len() {
+            if !assigned[j] && hamming_distance(hashes[i].1, hashes[j].1) <= threshold {
+                group.push(hashes[j].0.clone());
+                assigned[j] = true;
+            }
+        }
+        if group.len() >= 2 {
+            clusters.push(group);
+        }
+    }
+    clusters
+}
+
+/// Sample rate every acoustic fingerprint is computed at. Downsampling to a
+/// low mono rate discards the high-frequency detail that differs between
+/// bitrates/formats while keeping the pitch content that identifies a recording.
+const FINGERPRINT_SAMPLE_RATE: u32 = 11025;
+/// Analysis frame length in samples (~93 ms at 11025 Hz).
+const FINGERPRINT_FRAME: usize = 1024;
+/// Hop between successive frames (50% overlap).
+const FINGERPRINT_HOP: usize = 512;
+
+/// Fold a frame's spectral energy into 12 pitch-class (chroma) bins.
+///
+/// Rather than a full FFT, each candidate pitch across a handful of octaves is
+/// evaluated with a Goertzel-style magnitude and accumulated into its pitch
+/// class, which is enough to characterise the harmonic content of the frame.
+fn frame_chroma(frame: &[f32], sample_rate: u32) -> [f32; 12] {
+    let mut chroma = [0.0f32; 12];
+    // MIDI notes 36..=95 span C2..B6 — the range that carries most musical energy.
+    for midi in 36u32..96 {
+        let freq = 440.0 * 2f64.powf((midi as f64 - 69.0) / 12.0);
+        let k = 2.0 * std::f64::consts::PI * freq / sample_rate as f64;
+        let (mut real, mut imag) = (0.0f64, 0.0f64);
+        for (n, &s) in frame.iter().enumerate() {
+            let angle = k * n as f64;
+            real += s as f64 * angle.cos();
+            imag -= s as f64 * angle.sin();
+        }
+        let mag = (real * real + imag * imag).sqrt();
+        chroma[(midi % 12) as usize] += mag as f32;
+    }
+    chroma
+}
+
+/// Quantise a chroma vector into a compact 12-bit descriptor: one bit per
+/// pitch class, set when that class is above the frame's mean energy.
+fn quantize_chroma(chroma: &[f32; 12]) -> u16 {
+    let mean: f32 = chroma.iter().sum::<f32>() / 12.0;
+    let mut descriptor = 0u16;
+    for (i, &energy) in chroma.iter().enumerate() {
+        if energy > mean {
+            descriptor |= 1 << i;
+        }
+    }
+    descriptor
+}
+
+/// Build a per-frame fingerprint vector from mono PCM at [`FINGERPRINT_SAMPLE_RATE`].
+fn fingerprint_from_samples(samples: &[f32], sample_rate: u32) -> Vec<u16> {
+    let mut fp = Vec::new();
+    let mut start = 0;
+    while start + FINGERPRINT_FRAME <= samples.len() {
+        let frame = &samples[start..start + FINGERPRINT_FRAME];
+        fp.push(quantize_chroma(&frame_chroma(frame, sample_rate)));
+        start += FINGERPRINT_HOP;
+    }
+    fp
+}
+
+/// Normalised distance in [0, 1] between two fingerprints, minimised over the
+/// best offset alignment (shorter print slid along the longer one).
+///
+/// Distance is the averaged per-frame Hamming difference of the 12-bit
+/// descriptors over the overlapping region; identical recordings at different
+/// bitrates land near zero.
+fn compare_fingerprints(a: &[u16], b: &[u16]) -> f32 {
+    if a.is_empty() || b.is_empty() {
+        return 1.0;
+    }
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    let max_offset = long.len() - short.len();
+    let mut best = f32::MAX;
+    for offset in 0..=max_offset {
+        let mut diff_bits = 0u32;
+        for (i, &s) in short.iter().enumerate() {
+            diff_bits += (s ^ long[offset + i]).count_ones();
+        }
+        let normalized = diff_bits as f32 / (short.len() as f32 * 12.0);
+        if normalized < best {
+            best = normalized;
+        }
+    }
+    best
+}
+
+/// Greedily cluster fingerprints whose distance to a group's first member is at
+/// most `threshold`.
+///
+/// Mirrors [`cluster_by_hamming`] but over acoustic fingerprints; returns only
+/// groups with at least two members.
+fn cluster_fingerprints(prints: &[(PathBuf, Vec<u16>)], threshold: f32) -> Vec<Vec<PathBuf>> {
+    let mut assigned = vec![false; prints.len()];
+    let mut clusters = Vec::new();
+    for i in 0..prints.len() {
+        if assigned[i] {
+            continue;
+        }
+        let mut group = vec![prints[i].0.clone()];
+        assigned[i] = true;
+        for j in i + 1..prints.len() {
+            if !assigned[j] && compare_fingerprints(&prints[i].1, &prints[j].1) <= threshold {
+                group.push(prints[j].0.clone());
+                assigned[j] = true;
+            }
+        }
+        if group.len() >= 2 {
+            clusters.push(group);
+        }
+    }
+    clusters
+}
+
+/// Decode an audio file to mono PCM at [`FINGERPRINT_SAMPLE_RATE`].
+///
+/// Returns `None` if the file cannot be decoded. Channels are averaged to mono
+/// and the signal is nearest-neighbour resampled to the fixed analysis rate.
+fn decode_mono_pcm(path: &Path) -> Option<(Vec<f32>, u32)> {
+    use symphonia::core::audio::SampleBuffer;
+    use symphonia::core::codecs::DecoderOptions;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let file = std::fs::File::open(path).ok()?;
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+    let probed = symphonia::default::get_probe()
+        .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+        .ok()?;
+    let mut format = probed.format;
+    let track = format.default_track()?.clone();
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .ok()?;
+    let in_rate = track.codec_params.sample_rate.unwrap_or(FINGERPRINT_SAMPLE_RATE);
+
+    let mut mono = Vec::new();
+    while let Ok(packet) = format.next_packet() {
+        let Ok(decoded) = decoder.decode(&packet) else { continue };
+        let spec = *decoded.spec();
+        let mut buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+        buf.copy_interleaved_ref(decoded);
+        let channels = spec.channels.count().max(1);
+        for frame in buf.samples().chunks(channels) {
+            let avg: f32 = frame.iter().copied().sum::<f32>() / channels as f32;
+            mono.push(avg);
+        }
+    }
+    Some(resample_nearest(&mono, in_rate, FINGERPRINT_SAMPLE_RATE))
+}
+
+/// Nearest-neighbour resample `samples` from `from` to `to` Hz.
+fn resample_nearest(samples: &[f32], from: u32, to: u32) -> (Vec<f32>, u32) {
+    if from == to || samples.is_empty() {
+        return (samples.to_vec(), to);
+    }
+    let ratio = from as f64 / to as f64;
+    let out_len = ((samples.len() as f64) / ratio) as usize;
+    let mut out = Vec::with_capacity(out_len);
+    for i in 0..out_len {
+        let src = (i as f64 * ratio) as usize;
+        out.push(samples[src.min(samples.len() - 1)]);
+    }
+    (out, to)
+}
+
+/// Compute an acoustic fingerprint for `path`, consulting the on-disk sidecar
+/// cache keyed by path+mtime so rescans avoid re-decoding unchanged files.
+fn audio_fingerprint_cached(path: &Path) -> Option<Vec<u16>> {
+    let mtime = fs::metadata(path).and_then(|m| m.modified()).ok();
+    if let Some(fp) = read_fingerprint_sidecar(path, mtime) {
+        return Some(fp);
+    }
+    let (samples, rate) = decode_mono_pcm(path)?;
+    let fp = fingerprint_from_samples(&samples, rate);
+    if !fp.is_empty() {
+        write_fingerprint_sidecar(path, mtime, &fp);
+    }
+    Some(fp)
+}
+
+/// Path of the fingerprint sidecar that sits beside the track.
+fn fingerprint_sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".fpcache");
+    path.with_file_name(name)
+}
+
+/// Read a cached fingerprint if the sidecar's stored mtime still matches.
+///
+/// Sidecar layout: first line is the mtime as seconds since the Unix epoch,
+/// second line is the comma-separated descriptor values.
+fn read_fingerprint_sidecar(path: &Path, mtime: Option<SystemTime>) -> Option<Vec<u16>> {
+    let contents = fs::read_to_string(fingerprint_sidecar_path(path)).ok()?;
+    let mut lines = contents.lines();
+    let stamp: u64 = lines.next()?.trim().parse().ok()?;
+    let want = mtime
+        .and_then(|m| m.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())?;
+    if stamp != want {
+        return None;
+    }
+    let fp = lines
+        .next()?
+        .split(',')
+        .filter_map(|v| v.trim().parse().ok())
+        .collect();
+    Some(fp)
+}
+
+/// Persist a fingerprint to its sidecar, stamped with the track's mtime.
+fn write_fingerprint_sidecar(path: &Path, mtime: Option<SystemTime>, fp: &[u16]) {
+    let Some(stamp) = mtime
+        .and_then(|m| m.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+    else {
+        return;
+    };
+    let values = fp.iter().map(|v| v.to_string()).collect::<Vec<_>>().join(",");
+    let _ = fs::write(fingerprint_sidecar_path(path), format!("{}\n{}\n", stamp, values));
+}
+
+/// Within a cluster of visually-similar images, the copies worth deleting:
+/// everything except the largest file on disk (taken as the best quality).
+///
+/// Ties keep the first-seen path. Missing files count as zero bytes so they
+/// sort to the deletable side.
+fn lower_quality_image_copies(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let sized: Vec<(PathBuf, u64)> = paths
+        .iter()
+        .map(|p| (p.clone(), fs::metadata(p).map(|m| m.len()).unwrap_or(0)))
+        .collect();
+    // `min_by_key` returns the first of equal elements, so ties keep the first path.
+    let keep = sized
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, (_, s))| std::cmp::Reverse(*s))
+        .map(|(i, _)| i);
+    sized
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| Some(*i) != keep)
+        .map(|(_, (p, _))| p)
+        .collect()
+}
+
+/// Decode an image and reduce it to a 9×8 grayscale grid, then compute its dHash.
+fn dhash_image(path: &Path) -> Option<u64> {
+    use image::imageops::FilterType;
+    let img = image::open(path).ok()?.grayscale();
+    let small = img.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+    Some(compute_dhash(small.as_raw()))
+}
+
+/// Compute an image's dHash, consulting an on-disk sidecar cache keyed by
+/// path+size+mtime so rescans skip re-decoding unchanged photos. Mirrors
+/// [`audio_fingerprint_cached`]; files that fail to decode return `None`.
+fn dhash_image_cached(path: &Path) -> Option<u64> {
+    let (size, mtime) = fs::metadata(path)
+        .map(|m| (m.len(), m.modified().ok()))
+        .unwrap_or((0, None));
+    if let Some(hash) = read_dhash_sidecar(path, size, mtime) {
+        return Some(hash);
+    }
+    let hash = dhash_image(path)?;
+    write_dhash_sidecar(path, size, mtime, hash);
+    Some(hash)
+}
+
+/// Path of the dHash sidecar that sits beside the image.
+fn dhash_sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".dhcache");
+    path.with_file_name(name)
+}
+
+/// Fold a file's size and mtime into the stamp the sidecar is keyed by; `None`
+/// when the mtime is unavailable, which forces a recompute.
+fn dhash_stamp(size: u64, mtime: Option<SystemTime>) -> Option<String> {
+    let secs = mtime
+        .and_then(|m| m.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())?;
+    Some(format!("{}:{}", size, secs))
+}
+
+/// Read a cached dHash if the sidecar's stored stamp still matches.
+///
+/// Sidecar layout: first line is the `size:mtime` stamp, second line is the
+/// hash as a decimal `u64`.
+fn read_dhash_sidecar(path: &Path, size: u64, mtime: Option<SystemTime>) -> Option<u64> {
+    let want = dhash_stamp(size, mtime)?;
+    let contents = fs::read_to_string(dhash_sidecar_path(path)).ok()?;
+    let mut lines = contents.lines();
+    if lines.next()?.trim() != want {
+        return None;
+    }
+    lines.next()?.trim().parse().ok()
+}
+
+/// Persist a dHash to its sidecar, stamped with the image's size and mtime.
+fn write_dhash_sidecar(path: &Path, size: u64, mtime: Option<SystemTime>, hash: u64) {
+    let Some(stamp) = dhash_stamp(size, mtime) else {
+        return;
+    };
+    let _ = fs::write(dhash_sidecar_path(path), format!("{}\n{}\n", stamp, hash));
+}
+
+/// Default size-scan worker count: the machine's available parallelism,
+/// falling back to 4 when it can't be determined.
+fn default_size_worker_threads() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+}
+
+/// Standard user folders for the sidebar's quick-access section, resolved via
+/// the `dirs` crate. Only folders that actually exist are returned.
+fn quick_location_dirs() -> Vec<(&'static str, PathBuf)> {
+    let mut out = Vec::new();
+    if let Some(p) = dirs::desktop_dir() {
+        out.push(("üñ• Desktop", p));
+    }
+    if let Some(p) = dirs::document_dir() {
+        out.push(("üìÑ Documents", p));
+    }
+    if let Some(p) = dirs::download_dir() {
+        out.push(("‚¨áÔ∏è Downloads", p));
+    }
+    if let Some(p) = dirs::picture_dir() {
+        out.push(("üñº Pictures", p));
+    }
+    if let Some(p) = dirs::home_dir() {
+        out.push(("üè† Home", p));
+    }
+    out.into_iter().filter(|(_, p)| p.exists()).collect()
+}
+
+/// Path of the bookmark config file under the platform config directory.
+fn bookmarks_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("disk-dashboard").join("bookmarks.txt"))
+}
+
+/// Path of the exclude-glob config file under the platform config directory.
+fn exclude_globs_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("disk-dashboard").join("excludes.txt"))
+}
+
+/// Load persisted exclude globs (one pattern per line). Missing file ⇒ empty.
+fn load_exclude_globs() -> Vec<String> {
+    let Some(path) = exclude_globs_config_path() else { return Vec::new() };
+    fs::read_to_string(path)
+        .map(|s| s.lines().map(str::trim).filter(|l| !l.is_empty()).map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+/// Persist exclude globs, creating the config directory if needed.
+fn save_exclude_globs(patterns: &[String]) {
+    let Some(path) = exclude_globs_config_path() else { return };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(path, patterns.join("\n"));
+}
+
+/// Path of the delete-mode config file under the platform config directory.
+fn delete_mode_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("disk-dashboard").join("delete-mode.txt"))
+}
+
+/// Path of the classification-ruleset config file under the platform config
+/// directory. Users drop a `classification.json` here to override the built-in
+/// rules [`default_ruleset`] ships.
+fn classification_config_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("disk-dashboard").join("classification.json"))
+}
+
+/// Load the default delete behavior. Missing/unreadable file ⇒ trash (safe).
+fn load_delete_to_trash() -> bool {
+    let Some(path) = delete_mode_config_path() else { return true };
+    fs::read_to_string(path)
+        .map(|s| s.trim() != "permanent")
+        .unwrap_or(true)
+}
+
+/// Persist the default delete behavior, creating the config directory if needed.
+fn save_delete_to_trash(to_trash: bool) {
+    let Some(path) = delete_mode_config_path() else { return };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(path, if to_trash { "trash" } else { "permanent" });
+}
+
+/// Move `paths` to the OS trash, returning the number trashed, the freshly
+/// created trash entries (for Undo) and a list of per-path error strings.
+///
+/// `trash::delete` does not report the entries it creates, so the new items are
+/// recovered by diffing the trash listing around the operation and keeping only
+/// entries whose name matches one we actually deleted — this avoids adopting
+/// unrelated files another process may have trashed concurrently.
+fn trash_paths(paths: &[PathBuf]) -> (usize, Vec<trash::TrashItem>, Vec<String>) {
+    let before: HashSet<std::ffi::OsString> = trash::os_limited::list()
+        .map(|items| items.into_iter().map(|i| i.id).collect())
+        .unwrap_or_default();
+    let mut deleted_names: HashSet<std::ffi::OsString> = HashSet::new();
+
+    let mut deleted = 0;
+    let mut errors = Vec::new();
+    for path in paths {
+        match trash::delete(path) {
+            Ok(_) => {
+                deleted += 1;
+                if let Some(n) = path.file_name() {
+                    deleted_names.insert(n.to_os_string());
+                }
+            }
+            Err(e) => errors.push(format!(
+                "{}: {}",
+                path.file_name().unwrap_or_default().to_string_lossy(),
+                e
+            )),
+        }
+    }
+
+    let new_items = if deleted > 0 {
+        trash::os_limited::list()
+            .map(|items| items.into_iter()
+                .filter(|i| !before.contains(&i.id) && deleted_names.contains(&i.name))
+                .collect())
+            .unwrap_or_default()
+    } else {
+        Vec::new()
+    };
+
+    (deleted, new_items, errors)
+}
+
+/// Permanently remove `path` (file or directory tree) without using the trash.
+fn delete_permanently(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+/// Load persisted bookmarks (one path per line). Missing file ⇒ empty list.
+fn load_bookmarks() -> Vec<PathBuf> {
+    let Some(path) = bookmarks_config_path() else { return Vec::new() };
+    fs::read_to_string(path)
+        .map(|s| s.lines().filter(|l| !l.trim().is_empty()).map(PathBuf::from).collect())
+        .unwrap_or_default()
+}
+
+/// Persist bookmarks, creating the config directory if needed.
+fn save_bookmarks(bookmarks: &[PathBuf]) {
+    let Some(path) = bookmarks_config_path() else { return };
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let body = bookmarks
+        .iter()
+        .map(|p| p.to_string_lossy().to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let _ = fs::write(path, body);
+}
+
+/// Paths that can be safely deleted to collapse each duplicate group to a
+/// single copy: every member but the first, minus any protected path.
+///
+/// Keeping the first member guarantees at least one copy survives; the
+/// protected-path guard mirrors the one the delete handler enforces.
+fn duplicate_deletion_candidates(groups: &[DuplicateGroup]) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+    for group in groups {
+        for path in group.paths.iter().skip(1) {
+            if !is_protected_full_path(&path.to_string_lossy()) {
+                candidates.push(path.clone());
+            }
+        }
+    }
+    candidates
+}
+
+/// Which copies to keep when resolving a confirmed duplicate group.
+#[derive(Clone, Copy, PartialEq)]
+#[allow(dead_code)]
+enum DuplicateStrategy {
+    /// Keep only the most-recently-modified copy.
+    KeepOneNewest,
+    /// Keep only the oldest copy.
+    KeepOneOldest,
+    /// Keep every copy except the newest (delete the newest).
+    KeepAllExceptNewest,
+    /// Keep every copy except the oldest (delete the oldest).
+    KeepAllExceptOldest,
+}
+
+/// Return the member paths of `group` that the UI should mark for deletion under
+/// `strategy`. Protected paths are never returned. Ordering is by `modified`
+/// time; members whose mtime can't be read sort oldest, matching
+/// [`duplicate_stale_copies`].
+fn resolve_duplicates(group: &DuplicateGroup, strategy: DuplicateStrategy) -> Vec<PathBuf> {
+    let mtime = |p: &PathBuf| fs::metadata(p).and_then(|m| m.modified()).ok();
+    let newest = group.paths.iter().max_by_key(|p| mtime(p)).cloned();
+    let oldest = group.paths.iter().min_by_key(|p| mtime(p)).cloned();
+
+    let marked: Vec<PathBuf> = match strategy {
+        DuplicateStrategy::KeepOneNewest => group
+            .paths
+            .iter()
+            .filter(|p| Some(*p) != newest.as_ref())
+            .cloned()
+            .collect(),
+        DuplicateStrategy::KeepOneOldest => group
+            .paths
+            .iter()
+            .filter(|p| Some(*p) != oldest.as_ref())
+            .cloned()
+            .collect(),
+        DuplicateStrategy::KeepAllExceptNewest => newest.into_iter().collect(),
+        DuplicateStrategy::KeepAllExceptOldest => oldest.into_iter().collect(),
+    };
+
+    marked
+        .into_iter()
+        .filter(|p| !is_protected_full_path(&p.to_string_lossy()))
+        .collect()
+}
+
+/// Total bytes reclaimable across `groups` by collapsing each to a single copy.
+fn total_reclaimable(groups: &[DuplicateGroup]) -> u64 {
+    groups.iter().map(DuplicateGroup::reclaimable).sum()
+}
+
+/// Per duplicate group, the copies to delete when keeping the single newest
+/// file: everything except the most-recently-modified member, minus protected
+/// paths. Members whose mtime can't be read sort oldest.
+fn duplicate_stale_copies(groups: &[DuplicateGroup]) -> Vec<PathBuf> {
+    let mut stale = Vec::new();
+    for group in groups {
+        let newest = group
+            .paths
+            .iter()
+            .max_by_key(|p| fs::metadata(p).and_then(|m| m.modified()).ok())
+            .cloned();
+        for path in &group.paths {
+            if Some(path) == newest.as_ref() {
+                continue;
+            }
+            if !is_protected_full_path(&path.to_string_lossy()) {
+                stale.push(path.clone());
+            }
+        }
+    }
+    stale
+}
+
+/// Drop every group that has fewer than two members.
+///
+/// Singletons can never be duplicates, so pruning them after each grouping
+/// stage keeps the candidate set small before the more expensive next stage.
+fn prune_singleton_groups<K, V>(map: HashMap<K, Vec<V>>) -> Vec<Vec<V>> {
+    map.into_values().filter(|g| g.len() >= 2).collect()
+}
+
+/// Bucket `(path, size)` pairs by exact size, discarding unique sizes.
+///
+/// This is phase one of the duplicate pipeline: files with a unique size can
+/// never be byte-identical to anything else, so they are dropped up front.
+fn group_files_by_size(files: &[(PathBuf, u64)]) -> Vec<Vec<PathBuf>> {
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+    for (path, size) in files {
+        by_size.entry(*size).or_default().push(path.clone());
+    }
+    prune_singleton_groups(by_size)
+}
+
+/// How often (in entries) a long scan emits a [`ProgressData`] tick, so the
+/// channel isn't flooded on large trees.
+const PROGRESS_EVERY: usize = 64;
+
+/// Walk `root` and stream confirmed duplicate groups back over `sender`.
+///
+/// Uses the standard three-stage pipeline so most files are never fully hashed:
+/// group by size, then by a 16 KiB partial hash, then by a full content hash.
+/// Checks `cancel` between stages so navigation can abort a long scan promptly.
+fn scan_duplicates(root: &Path, cancel: Arc<AtomicBool>, sender: Sender<DuplicateGroup>) {
+    scan_duplicates_reporting(root, cancel, sender, None);
+}
+
+/// As [`scan_duplicates`], but emitting a [`ProgressData`] update on `progress`
+/// (when present) at the start of each stage and every [`PROGRESS_EVERY`]
+/// entries within it. The `cancel` flag is still honoured between batches so a
+/// scan can be stopped mid-flight.
+fn scan_duplicates_reporting(
+    root: &Path,
+    cancel: Arc<AtomicBool>,
+    sender: Sender<DuplicateGroup>,
+    progress: Option<Sender<ProgressData>>,
+) {
+    let report = |stage: u32, checked: usize, total: usize| {
+        if let Some(tx) = &progress {
+            let _ = tx.send(ProgressData {
+                current_stage: stage,
+                max_stage: 3,
+                entries_checked: checked,
+                entries_to_check: total,
+                tool_type: "duplicates",
+            });
+        }
+    };
+
+    // Stage 1 ("collecting"): walk the tree gathering (path, size) for every
+    // non-empty file.
+    let mut files: Vec<(PathBuf, u64)> = Vec::new();
+    let mut stack = vec![root.to_path_buf()];
+    report(1, 0, 0);
+    while let Some(dir) = stack.pop() {
+        if cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Ok(entries) = fs::read_dir(&dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                // Never descend into or hash protected system paths.
+                if is_protected_full_path(&path.to_string_lossy()) {
+                    continue;
+                }
+                match entry.metadata() {
+                    Ok(m) if m.is_dir() => stack.push(path),
+                    Ok(m) if m.is_file() && m.len() > 0 => {
+                        files.push((path, m.len()));
+                        if files.len() % PROGRESS_EVERY == 0 {
+                            report(1, files.len(), files.len());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let size_groups = group_files_by_size(&files);
+    let prefix_total: usize = size_groups.iter().map(|g| g.len()).sum();
+    let mut prefix_done = 0;
+    report(2, 0, prefix_total);
+
+    // Stage 2 ("pre-hash"): regroup surviving size-groups by a 16 KiB partial
+    // hash, collecting the candidates that still collide so the stage-3 total
+    // is known before that pass reports progress.
+    let mut candidate_groups: Vec<(u64, Vec<PathBuf>)> = Vec::new();
+    for paths in size_groups {
+        if cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        let size = fs::metadata(&paths[0]).map(|m| m.len()).unwrap_or(0);
+        let mut by_prefix: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+        for path in paths {
+            if let Ok(h) = hash_file_prefix(&path, 16 * 1024) {
+                by_prefix.entry(h).or_default().push(path);
+            }
+            prefix_done += 1;
+            if prefix_done % PROGRESS_EVERY == 0 {
+                report(2, prefix_done, prefix_total);
+            }
+        }
+        for candidates in prune_singleton_groups(by_prefix) {
+            candidate_groups.push((size, candidates));
+        }
+    }
+
+    // Stage 3 ("full-hash"): confirm each candidate with a full content hash.
+    let full_total: usize = candidate_groups.iter().map(|(_, c)| c.len()).sum();
+    let mut full_checked = 0;
+    report(3, 0, full_total);
+    for (size, candidates) in candidate_groups {
+        if cancel.load(Ordering::Relaxed) {
+            return;
+        }
+        let mut by_full: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+        for path in candidates {
+            if let Ok(h) = hash_file_full(&path) {
+                by_full.entry(h).or_default().push(path);
+            }
+            full_checked += 1;
+            if full_checked % PROGRESS_EVERY == 0 {
+                report(3, full_checked, full_total);
+            }
+        }
+        for (hash, group) in by_full {
+            if group.len() >= 2 {
+                let _ = sender.send(DuplicateGroup { hash, paths: group, size });
+            }
+        }
+    }
+}
+
+/// Group byte-identical files among already-scanned `items`, returning whole
+/// [`FileItem`]s so the multi-selection machinery can pre-select all-but-one in
+/// each group for deletion (see [`duplicate_extra_copies`]).
+///
+/// Uses the same three-stage pipeline as [`scan_duplicates`] so large sets stay
+/// fast: bucket by exact `size` (unique sizes can't collide and are dropped),
+/// split each bucket by a cheap 4 KiB prefix hash, then confirm survivors with a
+/// full-content hash. The per-size-group hashing runs in parallel with rayon.
+/// Directories and zero-byte files are ignored; every returned group has at
+/// least two members.
+fn find_duplicates(items: &[FileItem]) -> Vec<Vec<FileItem>> {
+    use rayon::prelude::*;
+
+    // Stage 1: bucket by exact size, dropping unique sizes up front.
+    let mut by_size: HashMap<u64, Vec<&FileItem>> = HashMap::new();
+    for item in items {
+        if !item.is_dir && item.size > 0 {
+            by_size.entry(item.size).or_default().push(item);
+        }
+    }
+    let size_groups: Vec<Vec<&FileItem>> =
+        by_size.into_values().filter(|g| g.len() >= 2).collect();
+
+    size_groups
+        .par_iter()
+        .flat_map(|group| {
+            // Stage 2: split by a 4 KiB prefix hash.
+            let mut by_prefix: HashMap<u64, Vec<&FileItem>> = HashMap::new();
+            for item in group {
+                if let Ok(h) = hash_file_prefix(&item.path, 4 * 1024) {
+                    by_prefix.entry(h).or_default().push(item);
+                }
+            }
+            // Stage 3: confirm survivors with a full-content hash.
+            let mut confirmed: Vec<Vec<FileItem>> = Vec::new();
+            for bucket in by_prefix.into_values().filter(|b| b.len() >= 2) {
+                let mut by_full: HashMap<u64, Vec<FileItem>> = HashMap::new();
+                for item in bucket {
+                    if let Ok(h) = hash_file_full(&item.path) {
+                        by_full.entry(h).or_default().push((*item).clone());
+                    }
+                }
+                confirmed.extend(by_full.into_values().filter(|b| b.len() >= 2));
+            }
+            confirmed
+        })
+        .collect()
+}
+
+/// Every copy in each duplicate group except the first (kept) one, flattened for
+/// dropping straight into the selection `HashSet<PathBuf>`.
+fn duplicate_extra_copies(groups: &[Vec<FileItem>]) -> Vec<PathBuf> {
+    groups
+        .iter()
+        .flat_map(|g| g.iter().skip(1).map(|i| i.path.clone()))
+        .collect()
+}
+
+/// Move a file or directory to `dst`, falling back to copy-then-delete across volumes.
+///
+/// `fs::rename` fails with a cross-device error when the source and destination
+/// live on different filesystems, so on failure we recreate the tree by copying
+/// every file and recreating directories, then remove the source.
+fn move_path(src: &Path, dst: &Path) -> std::io::Result<()> {
+    if fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+    if src.is_dir() {
+        copy_dir_recursive(src, dst)?;
+        fs::remove_dir_all(src)
+    } else {
+        fs::copy(src, dst)?;
+        fs::remove_file(src)
+    }
+}
+
+/// Recursively copy a directory tree, recreating subdirectories under `dst`.
+fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)?.flatten() {
+        let from = entry.path();
+        let to = dst.join(entry.file_name());
+        if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            copy_dir_recursive(&from, &to)?;
+        } else {
+            fs::copy(&from, &to)?;
+        }
+    }
+    Ok(())
+}
+
 /// Calculate the total size of a directory (non-recursive, just immediate children)
 fn calculate_dir_size_shallow(path: &Path) -> u64 {
     fs::read_dir(path)
@@ -1931,13 +5189,82 @@ fn calculate_dir_size_shallow(path: &Path) -> u64 {
         .unwrap_or(0)
 }
 
-/// Calculate the total size of a directory recursively (with depth limit)
-fn calculate_dir_size_recursive(path: &Path) -> u64 {
-    calculate_dir_size_recursive_limited(path, 2) // Limit to 2 levels to avoid UI freeze
+/// Calculate the total size of a directory recursively (with depth limit)
+fn calculate_dir_size_recursive(path: &Path, excludes: &[String]) -> u64 {
+    calculate_dir_size_recursive_limited(path, 2, excludes) // Limit to 2 levels to avoid UI freeze
+}
+
+/// Depth ceiling for the background size walk. Large enough to cover any real
+/// hierarchy while still bounding recursion on pathological (e.g. cyclic
+/// symlink) trees; the 8 MiB worker stack comfortably accommodates it.
+const SIZE_SCAN_MAX_DEPTH: u32 = 64;
+
+/// Calculate a directory's size while streaming progress and honouring cancellation.
+///
+/// Polls `cancel` between entries so navigating away aborts promptly, and sends
+/// a [`SizeUpdate::Progress`] roughly every 512 files so the UI can show a live
+/// indicator instead of a silent pending state.
+#[allow(clippy::too_many_arguments)]
+fn calculate_dir_size_streaming(
+    path: &Path,
+    max_depth: u32,
+    cancel: &Arc<AtomicBool>,
+    generation: u64,
+    sender: &Sender<SizeUpdate>,
+    files: &mut u64,
+    bytes: &mut u64,
+    excludes: &[String],
+) -> u64 {
+    if cancel.load(Ordering::Relaxed) {
+        return 0;
+    }
+    if max_depth == 0 {
+        return calculate_dir_size_shallow(path);
+    }
+
+    let mut total_size: u64 = 0;
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            if cancel.load(Ordering::Relaxed) {
+                return total_size;
+            }
+            let entry_path = entry.path();
+            // Glob-excluded entries are neither counted nor descended into.
+            if path_excluded(excludes, &entry_path) {
+                continue;
+            }
+            if let Ok(metadata) = entry.metadata() {
+                if metadata.is_file() {
+                    total_size = total_size.saturating_add(metadata.len());
+                    *files += 1;
+                    *bytes = bytes.saturating_add(metadata.len());
+                    if *files % 512 == 0 {
+                        let _ = sender.send(SizeUpdate::Progress {
+                            generation,
+                            files: *files,
+                            bytes: *bytes,
+                        });
+                    }
+                } else if metadata.is_dir() {
+                    let name = entry.file_name();
+                    let name_str = name.to_string_lossy().to_lowercase();
+                    if name_str.starts_with('$')
+                        || name_str == "system volume information"
+                        || name_str == "windows"
+                    {
+                        continue;
+                    }
+                    total_size = total_size.saturating_add(calculate_dir_size_streaming(
+                        &entry_path, max_depth - 1, cancel, generation, sender, files, bytes, excludes));
+                }
+            }
+        }
+    }
+    total_size
 }
 
 /// Calculate directory size with depth limit to prevent crashes
-fn calculate_dir_size_recursive_limited(path: &Path, max_depth: u32) -> u64 {
+fn calculate_dir_size_recursive_limited(path: &Path, max_depth: u32, excludes: &[String]) -> u64 {
     if max_depth == 0 {
         // At max depth, just return shallow size
         return calculate_dir_size_shallow(path);
@@ -1947,6 +5274,11 @@ fn calculate_dir_size_recursive_limited(path: &Path, max_depth: u32) -> u64 {
 
     if let Ok(entries) = fs::read_dir(path) {
         for entry in entries.flatten() {
+            let entry_path = entry.path();
+            // Glob-excluded entries are neither counted nor descended into.
+            if path_excluded(excludes, &entry_path) {
+                continue;
+            }
             if let Ok(metadata) = entry.metadata() {
                 if metadata.is_file() {
                     total_size = total_size.saturating_add(metadata.len());
@@ -1961,7 +5293,7 @@ fn calculate_dir_size_recursive_limited(path: &Path, max_depth: u32) -> u64 {
                     }
                     // Recursively calculate subdirectory size
                     total_size = total_size.saturating_add(
-                        calculate_dir_size_recursive_limited(&entry.path(), max_depth - 1)
+                        calculate_dir_size_recursive_limited(&entry_path, max_depth - 1, excludes)
                     );
                 }
             }
@@ -1971,12 +5303,178 @@ fn calculate_dir_size_recursive_limited(path: &Path, max_depth: u32) -> u64 {
     total_size
 }
 
+/// One compiled `.gitignore`-style rule.
+struct IgnoreRule {
+    /// The glob, normalised to `/` separators with any trailing `/` removed.
+    pattern: String,
+    /// A `!`-prefixed rule that re-includes a path an earlier rule excluded.
+    negate: bool,
+    /// Source line ended in `/`: the rule matches directories only.
+    dir_only: bool,
+}
+
+/// An ordered set of `.gitignore`-style ignore patterns consulted during a
+/// walk. Patterns match against the path relative to the scan root; rules are
+/// evaluated in order and the last match wins, so a `!`-negation re-includes a
+/// previously excluded path. A directory match prunes the whole subtree.
+#[derive(Default)]
+struct IgnoreSet {
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreSet {
+    /// Parse one pattern per line: blank lines and `#` comments are skipped, a
+    /// leading `!` negates, and a trailing `/` restricts the rule to directories.
+    fn parse(text: &str) -> IgnoreSet {
+        let mut rules = Vec::new();
+        for line in text.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negate, body) = match line.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, line),
+            };
+            let dir_only = body.ends_with('/');
+            let pattern = body.trim_end_matches('/').replace('\\', "/");
+            if pattern.is_empty() {
+                continue;
+            }
+            rules.push(IgnoreRule { pattern, negate, dir_only });
+        }
+        IgnoreSet { rules }
+    }
+
+    /// Read and parse a pattern file, returning an empty set on any read error.
+    fn from_file(path: &Path) -> IgnoreSet {
+        fs::read_to_string(path)
+            .map(|t| Self::parse(&t))
+            .unwrap_or_default()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether `rel` (a path relative to the scan root, `/`-separated) is
+    /// ignored. `is_dir` gates directory-only rules. The last matching rule
+    /// decides, so a later negation overrides an earlier exclude.
+    fn is_ignored(&self, rel: &str, is_dir: bool) -> bool {
+        let rel = rel.replace('\\', "/");
+        let base = rel.rsplit('/').next().unwrap_or(&rel);
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            if glob_match(&rule.pattern, &rel) || glob_match(&rule.pattern, base) {
+                ignored = !rule.negate;
+            }
+        }
+        ignored
+    }
+}
+
 /// Check if a folder should block navigation (empty folder)
 #[allow(dead_code)] // Used in tests
 fn should_block_folder_entry(child_count: Option<usize>) -> bool {
     child_count == Some(0)
 }
 
+/// Usefulness assigned to degenerate cleanup entries (empty files, empty
+/// folders, broken symlinks). Zero so they sort to the very top of a
+/// usefulness-ascending cleanup listing.
+const DEGENERATE_USEFULNESS: f32 = 0.0;
+
+/// Detect the degenerate entries a cleanup scan targets from a path: a symlink
+/// whose target no longer resolves ([`FileCategory::BrokenSymlink`]) or a
+/// zero-byte regular file ([`FileCategory::Empty`]). Empty directories are
+/// tagged by the caller, where the child count is known. Returns `None` for
+/// ordinary entries.
+///
+/// Broken-symlink detection reads the link and then `metadata`s the target;
+/// `metadata` resolves symlinks and returns an error (rather than looping) on a
+/// cycle, so a dangling or circular link classifies without following into it.
+fn degenerate_category(path: &Path, is_dir: bool, size: u64) -> Option<(FileCategory, f32)> {
+    if let Ok(meta) = fs::symlink_metadata(path) {
+        if meta.file_type().is_symlink() {
+            let resolves = fs::read_link(path)
+                .ok()
+                .map(|target| {
+                    let full = if target.is_absolute() {
+                        target
+                    } else if let Some(parent) = path.parent() {
+                        parent.join(target)
+                    } else {
+                        target
+                    };
+                    fs::metadata(&full).is_ok()
+                })
+                .unwrap_or(false);
+            if !resolves {
+                return Some((FileCategory::BrokenSymlink, DEGENERATE_USEFULNESS));
+            }
+        }
+    }
+    if !is_dir && size == 0 {
+        return Some((FileCategory::Empty, DEGENERATE_USEFULNESS));
+    }
+    None
+}
+
+/// Collect everything safe to reclaim as "empty" under `root`: zero-byte files
+/// and empty directories, including directories that are empty only once their
+/// empty children are removed.
+///
+/// Directories are evaluated bottom-up — a folder containing nothing but
+/// (now-reclaimed) empty subfolders is itself reported, with its children
+/// listed ahead of it so a caller deleting in order removes leaves first.
+/// Protected system files and paths ([`is_protected_path`] /
+/// [`is_protected_full_path`]) are never offered and keep their parent alive.
+/// `root` itself is never returned; only its descendants.
+fn find_empty_reclaimable(root: &Path) -> Vec<PathBuf> {
+    let mut empties = Vec::new();
+    collect_empty_entries(root, &mut empties);
+    empties
+}
+
+/// Recurse into `dir`, appending reclaimable empty files and directories to
+/// `out`, and return whether `dir` itself is empty (no surviving children), so
+/// the parent can reclaim it in turn.
+fn collect_empty_entries(dir: &Path, out: &mut Vec<PathBuf>) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        // Unreadable directory: treat as non-empty so it is never offered.
+        return false;
+    };
+    let mut all_reclaimed = true;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let protected = is_protected_full_path(&path.to_string_lossy())
+            || entry
+                .file_name()
+                .to_str()
+                .map(is_protected_path)
+                .unwrap_or(false);
+        if protected {
+            all_reclaimed = false;
+            continue;
+        }
+        match entry.metadata() {
+            Ok(m) if m.is_dir() => {
+                if collect_empty_entries(&path, out) {
+                    out.push(path);
+                } else {
+                    all_reclaimed = false;
+                }
+            }
+            Ok(m) if m.is_file() && m.len() == 0 => out.push(path),
+            _ => all_reclaimed = false,
+        }
+    }
+    all_reclaimed
+}
+
 /// Check if a path is a protected system path (name only)
 #[allow(dead_code)] // Used in tests
 fn is_protected_path(name: &str) -> bool {
@@ -2012,9 +5510,198 @@ fn is_protected_full_path(path: &str) -> bool {
     path_lower.contains("program files")
 }
 
+/// A single extension-keyed classification rule: when a file's extension
+/// matches, it takes this rule's [`FileCategory`] and a usefulness score —
+/// `base` normally, or the score of the first size band the file is large
+/// enough for. Rules are data so they can be shipped in a user config instead
+/// of being recompiled; see [`default_ruleset`].
+#[derive(Clone, Debug)]
+struct ClassificationRule {
+    /// Lowercase extensions (no leading dot) this rule applies to.
+    extensions: Vec<String>,
+    category: FileCategory,
+    base: f32,
+    /// `(min_bytes, usefulness)` overrides ordered highest-threshold first; the
+    /// first band whose `min_bytes` the file meets wins over `base`, mirroring
+    /// the size-dependent scores videos and archives already use.
+    size_bands: Vec<(u64, f32)>,
+}
+
+impl ClassificationRule {
+    /// Usefulness for a file of `size`: the first matching size band, else `base`.
+    fn score(&self, size: u64) -> f32 {
+        self.size_bands
+            .iter()
+            .find(|(min, _)| size >= *min)
+            .map(|(_, s)| *s)
+            .unwrap_or(self.base)
+    }
+}
+
+/// The ordered set of extension rules consulted by [`categorize_file`]. The
+/// first rule matching a file's extension wins; extensions never overlap across
+/// the built-in rules, so the order only matters for user-supplied overrides.
+#[derive(Clone, Debug)]
+struct ClassificationRuleset {
+    rules: Vec<ClassificationRule>,
+}
+
+impl ClassificationRuleset {
+    /// Resolve an extension and size to a `(category, usefulness)` pair, or
+    /// `None` when no rule claims the extension.
+    fn lookup(&self, ext: &str, size: u64) -> Option<(FileCategory, f32)> {
+        self.rules
+            .iter()
+            .find(|r| r.extensions.iter().any(|e| e == ext))
+            .map(|r| (r.category, r.score(size)))
+    }
+}
+
+/// The built-in ruleset, identical to the extension tables and usefulness
+/// numbers [`categorize_file`] used before it became data-driven. Used when no
+/// config file is present or one fails to parse.
+fn default_ruleset() -> ClassificationRuleset {
+    let rule = |exts: &[&str], category, base, size_bands: &[(u64, f32)]| ClassificationRule {
+        extensions: exts.iter().map(|e| e.to_string()).collect(),
+        category,
+        base,
+        size_bands: size_bands.to_vec(),
+    };
+    ClassificationRuleset {
+        rules: vec![
+            rule(
+                &["doc", "docx", "xls", "xlsx", "ppt", "pptx", "pdf", "txt", "md", "rtf", "odt",
+                  "ods", "odp"],
+                FileCategory::Regular,
+                90.0,
+                &[],
+            ),
+            rule(
+                &["jpg", "jpeg", "png", "gif", "bmp", "webp", "raw", "cr2", "nef", "arw"],
+                FileCategory::Regular,
+                95.0,
+                &[],
+            ),
+            rule(
+                &["mp4", "avi", "mkv", "mov", "wmv", "flv", "webm", "m4v"],
+                FileCategory::Regular,
+                85.0,
+                &[(1_000_000_000, 70.0)],
+            ),
+            rule(
+                &["mp3", "wav", "flac", "ogg", "aac", "m4a", "wma"],
+                FileCategory::Regular,
+                80.0,
+                &[],
+            ),
+            rule(
+                &["rs", "py", "js", "ts", "java", "c", "cpp", "h", "cs", "go", "html", "css",
+                  "json", "xml", "yaml", "toml", "sql"],
+                FileCategory::Regular,
+                85.0,
+                &[],
+            ),
+            rule(
+                &["zip", "rar", "7z", "tar", "gz", "bz2"],
+                FileCategory::Regular,
+                55.0,
+                &[(1_000_000_000, 30.0), (100_000_000, 45.0)],
+            ),
+            rule(&["iso", "dmg", "img"], FileCategory::Regular, 25.0, &[]),
+        ],
+    }
+}
+
+/// Map a config category string (case-insensitive) to a [`FileCategory`].
+fn parse_category(name: &str) -> Option<FileCategory> {
+    match name.to_lowercase().as_str() {
+        "mustkeep" | "must_keep" | "must keep" => Some(FileCategory::MustKeep),
+        "system" => Some(FileCategory::System),
+        "regular" => Some(FileCategory::Regular),
+        "useless" => Some(FileCategory::Useless),
+        _ => None,
+    }
+}
+
+/// Parse a classification ruleset from JSON, falling back to [`default_ruleset`]
+/// when the document is malformed. The expected shape is an array of objects:
+///
+/// ```json
+/// [{"extensions": ["heic", "avif"], "category": "Regular", "base": 95,
+///   "size_bands": [[1000000000, 70]]}]
+/// ```
+///
+/// A rule missing its extension list or naming an unknown category is skipped;
+/// an empty or unparseable document yields the defaults so classification never
+/// silently breaks on a bad config.
+fn parse_ruleset_json(json: &str) -> ClassificationRuleset {
+    let Ok(serde_json::Value::Array(items)) = serde_json::from_str::<serde_json::Value>(json) else {
+        return default_ruleset();
+    };
+    let mut rules = Vec::new();
+    for item in &items {
+        let extensions: Vec<String> = item
+            .get("extensions")
+            .and_then(|v| v.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|v| v.as_str())
+                    .map(|s| s.trim_start_matches('.').to_lowercase())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let category = item
+            .get("category")
+            .and_then(|v| v.as_str())
+            .and_then(parse_category);
+        let (extensions, category) = match (extensions, category) {
+            (exts, Some(cat)) if !exts.is_empty() => (exts, cat),
+            _ => continue,
+        };
+        let base = item.get("base").and_then(|v| v.as_f64()).unwrap_or(60.0) as f32;
+        let size_bands = item
+            .get("size_bands")
+            .and_then(|v| v.as_array())
+            .map(|bands| {
+                let mut parsed: Vec<(u64, f32)> = bands
+                    .iter()
+                    .filter_map(|b| b.as_array())
+                    .filter_map(|pair| {
+                        Some((pair.first()?.as_u64()?, pair.get(1)?.as_f64()? as f32))
+                    })
+                    .collect();
+                // Highest threshold first so the steepest band wins.
+                parsed.sort_by(|a, b| b.0.cmp(&a.0));
+                parsed
+            })
+            .unwrap_or_default();
+        rules.push(ClassificationRule { extensions, category, base, size_bands });
+    }
+    if rules.is_empty() {
+        default_ruleset()
+    } else {
+        ClassificationRuleset { rules }
+    }
+}
+
+/// Load the classification ruleset from the user config, or the built-in
+/// defaults when no config exists or it can't be read.
+fn load_ruleset() -> ClassificationRuleset {
+    let Some(path) = classification_config_path() else { return default_ruleset() };
+    match fs::read_to_string(path) {
+        Ok(json) => parse_ruleset_json(&json),
+        Err(_) => default_ruleset(),
+    }
+}
+
+/// The process-wide ruleset, parsed once on first use from config or defaults.
+fn ruleset() -> &'static ClassificationRuleset {
+    static RULESET: std::sync::OnceLock<ClassificationRuleset> = std::sync::OnceLock::new();
+    RULESET.get_or_init(load_ruleset)
+}
+
 /// Categorize a file based on its path, name, type, and size
 /// Returns (FileCategory, usefulness_score)
-#[allow(dead_code)] // Used in tests
 fn categorize_file(path: &str, name: &str, is_dir: bool, size: u64) -> (FileCategory, f32) {
     let name_lower = name.to_lowercase();
     let path_lower = path.to_lowercase();
@@ -2036,6 +5723,12 @@ fn categorize_file(path: &str, name: &str, is_dir: bool, size: u64) -> (FileCate
         return (FileCategory::MustKeep, 100.0);
     }
 
+    // Degenerate entries (broken symlinks, zero-byte files) are surfaced with
+    // their own cleanup categories.
+    if let Some(result) = degenerate_category(Path::new(path), is_dir, size) {
+        return result;
+    }
+
     // Temp files and cache - useless (safe to delete)
     if name_lower.contains("temp") ||
        name_lower.contains("cache") ||
@@ -2059,57 +5752,16 @@ fn categorize_file(path: &str, name: &str, is_dir: bool, size: u64) -> (FileCate
     }
 
     // Get file extension
-    let ext = std::path::Path::new(path)
-        .extension()
-        .and_then(|e| e.to_str())
-        .map(|e| e.to_lowercase())
-        .unwrap_or_default();
-
-    // Important user data - high usefulness
-    let important_extensions = ["doc", "docx", "xls", "xlsx", "ppt", "pptx", "pdf",
-                                "txt", "md", "rtf", "odt", "ods", "odp"];
-    if important_extensions.contains(&ext.as_str()) {
-        return (FileCategory::Regular, 90.0);
-    }
-
-    // Photos - very important to users
-    let photo_extensions = ["jpg", "jpeg", "png", "gif", "bmp", "webp", "raw", "cr2", "nef", "arw"];
-    if photo_extensions.contains(&ext.as_str()) {
-        return (FileCategory::Regular, 95.0);
-    }
-
-    // Videos - important but large
-    let video_extensions = ["mp4", "avi", "mkv", "mov", "wmv", "flv", "webm", "m4v"];
-    if video_extensions.contains(&ext.as_str()) {
-        let usefulness = if size > 1_000_000_000 { 70.0 } else { 85.0 };
-        return (FileCategory::Regular, usefulness);
-    }
-
-    // Audio - important
-    let audio_extensions = ["mp3", "wav", "flac", "ogg", "aac", "m4a", "wma"];
-    if audio_extensions.contains(&ext.as_str()) {
-        return (FileCategory::Regular, 80.0);
-    }
-
-    // Code and projects - important for developers
-    let code_extensions = ["rs", "py", "js", "ts", "java", "c", "cpp", "h", "cs", "go",
-                          "html", "css", "json", "xml", "yaml", "toml", "sql"];
-    if code_extensions.contains(&ext.as_str()) {
-        return (FileCategory::Regular, 85.0);
-    }
-
-    // Archives - depends on size
-    let archive_extensions = ["zip", "rar", "7z", "tar", "gz", "bz2"];
-    if archive_extensions.contains(&ext.as_str()) {
-        let usefulness = if size > 1_000_000_000 { 30.0 }
-                        else if size > 100_000_000 { 45.0 }
-                        else { 55.0 };
-        return (FileCategory::Regular, usefulness);
-    }
-
-    // ISOs and disk images - usually can be deleted
-    if ext == "iso" || ext == "dmg" || ext == "img" {
-        return (FileCategory::Regular, 25.0);
+    let ext = lowercase_extension(path);
+
+    // Extension-keyed rules (important data, photos, video, audio, code,
+    // archives, disk images) come from the loaded ruleset so users can retune
+    // scores or add formats without recompiling. The size-band thresholds that
+    // once lived inline (video >1 GB, archive >100 MB/1 GB) are encoded there.
+    if !ext.is_empty() {
+        if let Some(result) = ruleset().lookup(&ext, size) {
+            return result;
+        }
     }
 
     // Executables and installers
@@ -2149,6 +5801,285 @@ fn categorize_file(path: &str, name: &str, is_dir: bool, size: u64) -> (FileCate
     (FileCategory::Regular, usefulness)
 }
 
+/// Result of comparing a file's real format (sniffed from its leading bytes)
+/// against the type implied by its extension. See [`verify_extension`].
+#[derive(Clone, PartialEq, Debug)]
+#[allow(dead_code)]
+enum ExtensionVerdict {
+    /// The detected format is compatible with the declared extension.
+    Ok,
+    /// The real format disagrees with the extension; the file looks disguised
+    /// or mislabeled. Carries the declared extension and the detected type.
+    Mismatch { declared: String, real: &'static str },
+    /// The file was empty, unreadable, or its header matched no known
+    /// signature, so no verdict can be given.
+    NoVerdict,
+}
+
+/// Identify a file format from its magic bytes, returning a short family name
+/// (`"pdf"`, `"zip"`, `"jpeg"`, ...) or `None` when no signature matches. The
+/// zip signature covers the whole OOXML/OpenDocument container family.
+fn detect_format(header: &[u8]) -> Option<&'static str> {
+    let starts = |sig: &[u8]| header.len() >= sig.len() && &header[..sig.len()] == sig;
+    if starts(b"%PDF") {
+        Some("pdf")
+    } else if starts(b"PK\x03\x04") {
+        Some("zip")
+    } else if starts(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpeg")
+    } else if starts(&[0x89, b'P', b'N', b'G']) {
+        Some("png")
+    } else if starts(b"GIF8") {
+        Some("gif")
+    } else if starts(b"ID3") || starts(&[0xFF, 0xFB]) {
+        Some("mp3")
+    } else if starts(b"RIFF") && header.len() >= 12 && &header[8..12] == b"WAVE" {
+        Some("wav")
+    } else if starts(&[0x1F, 0x8B]) {
+        Some("gz")
+    } else if starts(&[0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C]) {
+        Some("7z")
+    } else if starts(b"MZ") {
+        Some("exe")
+    } else {
+        None
+    }
+}
+
+/// Extensions that are legitimately backed by a given detected format. Entries
+/// with several members (e.g. `zip`) are container formats whose files share
+/// one magic signature, so they must not be flagged against each other.
+fn extensions_for_format(format: &str) -> &'static [&'static str] {
+    match format {
+        "pdf" => &["pdf"],
+        "zip" => &["zip", "docx", "xlsx", "pptx", "odt", "ods", "odp", "jar", "apk", "epub"],
+        "jpeg" => &["jpg", "jpeg", "jpe", "jfif"],
+        "png" => &["png"],
+        "gif" => &["gif"],
+        "mp3" => &["mp3"],
+        "wav" => &["wav"],
+        "gz" => &["gz", "tgz"],
+        "7z" => &["7z"],
+        "exe" => &["exe", "dll", "sys", "ocx", "scr", "msi"],
+        _ => &[],
+    }
+}
+
+/// Whether `ext` (any case, no leading dot) names a format we have a magic
+/// signature for, and is therefore worth sniffing. Lets the scanner skip the
+/// header read for the overwhelmingly common text/source extensions.
+fn declares_known_signature(ext: &str) -> bool {
+    let ext = ext.to_lowercase();
+    ["pdf", "zip", "jpeg", "png", "gif", "mp3", "wav", "gz", "7z", "exe"]
+        .iter()
+        .any(|fmt| extensions_for_format(fmt).contains(&ext.as_str()))
+}
+
+/// Read the first bytes of `path` and compare the real format to `declared_ext`
+/// (the extension without a leading dot, any case). Mislabeled or disguised
+/// files return [`ExtensionVerdict::Mismatch`]; empty, unreadable, or
+/// unrecognized files return [`ExtensionVerdict::NoVerdict`].
+#[allow(dead_code)] // surfaced on demand from the file list
+fn verify_extension(path: &str, declared_ext: &str) -> ExtensionVerdict {
+    let declared = declared_ext.trim_start_matches('.').to_lowercase();
+    if declared.is_empty() {
+        return ExtensionVerdict::NoVerdict;
+    }
+    use std::io::Read;
+    let mut header = [0u8; 16];
+    let read = match fs::File::open(path).and_then(|mut f| f.read(&mut header)) {
+        Ok(n) if n > 0 => n,
+        _ => return ExtensionVerdict::NoVerdict,
+    };
+    match detect_format(&header[..read]) {
+        Some(real) => {
+            if extensions_for_format(real).contains(&declared.as_str()) {
+                ExtensionVerdict::Ok
+            } else {
+                ExtensionVerdict::Mismatch { declared, real }
+            }
+        }
+        None => ExtensionVerdict::NoVerdict,
+    }
+}
+
+/// Edge length, in pixels, of a generated file-list thumbnail.
+const THUMBNAIL_PX: u32 = 32;
+/// Upper bound on thumbnails kept decoded in memory at once.
+const THUMBNAIL_CACHE_CAPACITY: usize = 256;
+
+/// How a file-list row's icon is drawn. The list decides *what* a row is
+/// (its extension and [`FileCategory`]); this layer decides *how* it appears —
+/// a named theme icon, a generated pixel preview, or an emoji glyph fallback.
+#[allow(dead_code)]
+enum IconSource {
+    /// A freedesktop-style theme icon name, e.g. `"image-x-generic"`.
+    Theme(&'static str),
+    /// A decoded RGBA preview for a visual file type.
+    Thumbnail(egui::TextureHandle),
+    /// Emoji glyph used when no theme icon applies and thumbnailing is off or
+    /// failed.
+    Glyph(&'static str),
+}
+
+/// Freedesktop-style theme icon name for a file, independent of how it is
+/// finally rendered. Broad extension families map to the usual `*-x-generic`
+/// names; anything unrecognised falls back to a [`FileCategory`]-derived name.
+fn theme_icon_name(ext: &str, is_dir: bool, category: FileCategory) -> &'static str {
+    if is_dir {
+        return "folder";
+    }
+    match ext {
+        "jpg" | "jpeg" | "png" | "gif" | "bmp" | "webp" | "svg" | "ico" => "image-x-generic",
+        "mp4" | "avi" | "mkv" | "mov" | "wmv" | "flv" | "webm" => "video-x-generic",
+        "mp3" | "wav" | "flac" | "ogg" | "aac" | "m4a" => "audio-x-generic",
+        "pdf" => "application-pdf",
+        "doc" | "docx" | "xls" | "xlsx" | "ppt" | "pptx" => "x-office-document",
+        "txt" | "md" | "rtf" => "text-x-generic",
+        "rs" | "py" | "js" | "ts" | "java" | "c" | "cpp" | "h" | "cs" | "go" | "html" | "css"
+        | "json" | "xml" | "yaml" | "toml" => "text-x-source",
+        "zip" | "rar" | "7z" | "tar" | "gz" | "bz2" => "package-x-generic",
+        "exe" | "msi" | "bat" | "cmd" | "ps1" | "sh" => "application-x-executable",
+        _ => match category {
+            FileCategory::MustKeep => "emblem-important",
+            FileCategory::System => "emblem-system",
+            FileCategory::Useless => "user-trash",
+            FileCategory::Suspicious => "dialog-warning",
+            FileCategory::SimilarImage => "image-x-generic",
+            FileCategory::Empty => "text-x-generic",
+            FileCategory::EmptyFolder => "folder",
+            FileCategory::BrokenSymlink => "emblem-symbolic-link",
+            FileCategory::Duplicate | FileCategory::Regular | FileCategory::Unknown => {
+                "text-x-generic"
+            }
+        },
+    }
+}
+
+/// Whether `ext` (lowercase, no dot) names a file type we can render a real
+/// preview for. Images decode directly; videos go through [`decode_video_frame`].
+fn is_thumbnailable(ext: &str) -> bool {
+    PHOTO_EXTENSIONS.contains(&ext) || VIDEO_EXTENSIONS.contains(&ext)
+}
+
+/// Pull a single representative frame from a video a few seconds in using
+/// `ffmpeg`, returning the decoded image or `None` when ffmpeg is missing or
+/// the extraction fails.
+fn decode_video_frame(path: &Path) -> Option<image::DynamicImage> {
+    use std::process::Command;
+    let tmp = std::env::temp_dir().join(format!(
+        "dcd_thumb_{}.png",
+        path.to_string_lossy().bytes().fold(0u64, |h, b| h.wrapping_mul(31).wrapping_add(b as u64))
+    ));
+    let status = Command::new("ffmpeg")
+        .args(["-v", "error", "-y", "-ss", "3", "-i"])
+        .arg(path)
+        .args(["-frames:v", "1"])
+        .arg(&tmp)
+        .status()
+        .ok()?;
+    let img = if status.success() {
+        image::open(&tmp).ok()
+    } else {
+        None
+    };
+    let _ = fs::remove_file(&tmp);
+    img
+}
+
+/// Decode and downscale `path` to a square RGBA preview suitable for the file
+/// list, or `None` when the file can't be decoded.
+fn generate_thumbnail(path: &Path) -> Option<egui::ColorImage> {
+    use image::imageops::FilterType;
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    let img = if VIDEO_EXTENSIONS.contains(&ext.as_str()) {
+        decode_video_frame(path)?
+    } else {
+        image::open(path).ok()?
+    };
+    let small = img
+        .resize(THUMBNAIL_PX, THUMBNAIL_PX, FilterType::Triangle)
+        .to_rgba8();
+    let size = [small.width() as usize, small.height() as usize];
+    Some(egui::ColorImage::from_rgba_unmultiplied(size, small.as_raw()))
+}
+
+/// Bounded in-memory cache of generated thumbnails, keyed by path and stamped
+/// with the file's mtime so a rewritten file regenerates. Least-recently
+/// inserted entries are evicted once [`THUMBNAIL_CACHE_CAPACITY`] is exceeded,
+/// keeping memory bounded during long browsing sessions.
+struct ThumbnailCache {
+    entries: HashMap<PathBuf, (Option<SystemTime>, egui::TextureHandle)>,
+    order: VecDeque<PathBuf>,
+    capacity: usize,
+}
+
+impl ThumbnailCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Drop every cached texture (e.g. when previews are turned off).
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    /// Fetch the preview for `path`, generating and caching it on a miss or when
+    /// the stored mtime no longer matches. Returns `None` if it can't be decoded.
+    fn get(&mut self, ctx: &egui::Context, path: &Path) -> Option<egui::TextureHandle> {
+        let mtime = fs::metadata(path).and_then(|m| m.modified()).ok();
+        if let Some((stamp, tex)) = self.entries.get(path) {
+            if *stamp == mtime {
+                return Some(tex.clone());
+            }
+        }
+        let image = generate_thumbnail(path)?;
+        let tex = ctx.load_texture(path.to_string_lossy(), image, egui::TextureOptions::LINEAR);
+        if self.entries.insert(path.to_path_buf(), (mtime, tex.clone())).is_none() {
+            self.order.push_back(path.to_path_buf());
+            while self.order.len() > self.capacity {
+                if let Some(evicted) = self.order.pop_front() {
+                    self.entries.remove(&evicted);
+                }
+            }
+        }
+        Some(tex)
+    }
+}
+
+/// Decide how a file-list row's icon should be drawn, generating a thumbnail
+/// through `cache` for visual types when `thumbnails_enabled`, and otherwise
+/// falling back to the file's theme icon.
+fn icon_source(
+    ctx: &egui::Context,
+    cache: &mut ThumbnailCache,
+    path: &Path,
+    is_dir: bool,
+    category: FileCategory,
+    thumbnails_enabled: bool,
+) -> IconSource {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase())
+        .unwrap_or_default();
+    if !is_dir && thumbnails_enabled && is_thumbnailable(&ext) {
+        if let Some(tex) = cache.get(ctx, path) {
+            return IconSource::Thumbnail(tex);
+        }
+    }
+    IconSource::Theme(theme_icon_name(&ext, is_dir, category))
+}
+
 /// Get the icon for a file based on its extension and category
 #[allow(dead_code)] // Used in tests
 fn get_file_icon(name: &str, is_dir: bool, is_empty_folder: bool, category: FileCategory) -> &'static str {
@@ -2188,6 +6119,12 @@ fn get_file_icon(name: &str, is_dir: bool, is_empty_folder: bool, category: File
             FileCategory::System => "‚öôÔ∏è",
             FileCategory::Regular => "üìÑ",
             FileCategory::Useless => "üóëÔ∏è",
+            FileCategory::Duplicate => "DUP",
+            FileCategory::Suspicious => "‚ö†Ô∏è",
+            FileCategory::SimilarImage => "üñº",
+            FileCategory::Empty => "‚óã",
+            FileCategory::EmptyFolder => "üìÇ",
+            FileCategory::BrokenSymlink => "üîó",
             FileCategory::Unknown => "‚ùì",
         }
     }
@@ -2213,28 +6150,268 @@ fn compare_file_items(a: &FileItem, b: &FileItem, sort_column: SortColumn, ascen
                     FileCategory::System => 1,
                     FileCategory::Regular => 2,
                     FileCategory::Useless => 3,
-                    FileCategory::Unknown => 4,
+                    FileCategory::Suspicious => 4,
+                    FileCategory::Duplicate => 5,
+                    FileCategory::SimilarImage => 6,
+                    FileCategory::Empty => 7,
+                    FileCategory::EmptyFolder => 8,
+                    FileCategory::BrokenSymlink => 9,
+                    FileCategory::Unknown => 10,
                 }
             };
             cat_order(&a.category).cmp(&cat_order(&b.category))
         }
-        SortColumn::Usefulness => a.usefulness.partial_cmp(&b.usefulness).unwrap_or(std::cmp::Ordering::Equal),
-    };
+        SortColumn::Usefulness => a.usefulness.partial_cmp(&b.usefulness).unwrap_or(std::cmp::Ordering::Equal),
+        SortColumn::DuplicateGroup => a.size.cmp(&b.size).then_with(|| a.name.cmp(&b.name)),
+    };
+
+    if ascending { ordering } else { ordering.reverse() }
+}
+
+/// One atom of a [`RegexPattern`]: any char, a literal, or a character class.
+#[derive(Clone, Debug)]
+enum RegexAtom {
+    Any,
+    Literal(char),
+    /// A `[...]` class; `negate` is set for a leading `^`. Ranges are inclusive.
+    Class { negate: bool, ranges: Vec<(char, char)> },
+}
+
+impl RegexAtom {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            RegexAtom::Any => true,
+            RegexAtom::Literal(l) => *l == c,
+            RegexAtom::Class { negate, ranges } => {
+                let hit = ranges.iter().any(|(lo, hi)| c >= *lo && c <= *hi);
+                hit != *negate
+            }
+        }
+    }
+}
+
+/// Repetition applied to a [`RegexAtom`].
+#[derive(Clone, Copy, Debug)]
+enum RegexQuant {
+    One,
+    ZeroOrOne,
+    ZeroOrMore,
+    OneOrMore,
+}
+
+/// A deliberately small, anchored regular-expression matcher in the spirit of
+/// [`glob_match`]: it supports `.`, character classes `[...]` (ranges and a
+/// leading `^` negation), and the `*`, `+`, `?` quantifiers, and always matches
+/// the whole string (leading `^` / trailing `$` are accepted and ignored). It is
+/// not a full regex engine — no groups, alternation, or back-references.
+#[derive(Clone, Debug)]
+struct RegexPattern {
+    atoms: Vec<(RegexAtom, RegexQuant)>,
+}
+
+impl RegexPattern {
+    /// Compile `src` (case-insensitively) into a matcher.
+    fn compile(src: &str) -> RegexPattern {
+        let chars: Vec<char> = src.to_lowercase().chars().collect();
+        let mut atoms = Vec::new();
+        // Strip anchors; matching is already whole-string.
+        let start = if chars.first() == Some(&'^') { 1 } else { 0 };
+        let end = if chars.last() == Some(&'$') { chars.len() - 1 } else { chars.len() };
+        let mut i = start;
+        while i < end {
+            let atom = if chars[i] == '.' {
+                i += 1;
+                RegexAtom::Any
+            } else if chars[i] == '[' {
+                let (atom, next) = Self::parse_class(&chars, i, end);
+                i = next;
+                atom
+            } else {
+                // Treat `\x` as the literal `x`.
+                if chars[i] == '\\' && i + 1 < end {
+                    i += 1;
+                }
+                let c = chars[i];
+                i += 1;
+                RegexAtom::Literal(c)
+            };
+            let quant = match chars.get(i) {
+                Some('*') => { i += 1; RegexQuant::ZeroOrMore }
+                Some('+') => { i += 1; RegexQuant::OneOrMore }
+                Some('?') => { i += 1; RegexQuant::ZeroOrOne }
+                _ => RegexQuant::One,
+            };
+            atoms.push((atom, quant));
+        }
+        RegexPattern { atoms }
+    }
+
+    /// Parse a `[...]` class beginning at `open`, returning the atom and the
+    /// index just past the closing `]` (or end of pattern if unterminated).
+    fn parse_class(chars: &[char], open: usize, end: usize) -> (RegexAtom, usize) {
+        let mut i = open + 1;
+        let negate = chars.get(i) == Some(&'^');
+        if negate {
+            i += 1;
+        }
+        let mut ranges = Vec::new();
+        while i < end && chars[i] != ']' {
+            if i + 2 < end && chars[i + 1] == '-' && chars[i + 2] != ']' {
+                ranges.push((chars[i], chars[i + 2]));
+                i += 3;
+            } else {
+                ranges.push((chars[i], chars[i]));
+                i += 1;
+            }
+        }
+        if i < end && chars[i] == ']' {
+            i += 1; // consume ']'
+        }
+        (RegexAtom::Class { negate, ranges }, i)
+    }
+
+    /// Whether the whole of `text` matches.
+    fn is_match(&self, text: &str) -> bool {
+        let t: Vec<char> = text.to_lowercase().chars().collect();
+        self.match_from(0, &t, 0)
+    }
+
+    fn match_from(&self, ai: usize, text: &[char], ti: usize) -> bool {
+        let Some((atom, quant)) = self.atoms.get(ai) else {
+            return ti == text.len(); // anchored end
+        };
+        match quant {
+            RegexQuant::One => {
+                ti < text.len() && atom.matches(text[ti]) && self.match_from(ai + 1, text, ti + 1)
+            }
+            RegexQuant::ZeroOrOne => {
+                (ti < text.len()
+                    && atom.matches(text[ti])
+                    && self.match_from(ai + 1, text, ti + 1))
+                    || self.match_from(ai + 1, text, ti)
+            }
+            RegexQuant::ZeroOrMore | RegexQuant::OneOrMore => {
+                let mut run = 0;
+                while ti + run < text.len() && atom.matches(text[ti + run]) {
+                    run += 1;
+                }
+                let floor = if matches!(quant, RegexQuant::OneOrMore) { 1 } else { 0 };
+                // Greedy, backtracking down to the required minimum.
+                while run + 1 > floor {
+                    if self.match_from(ai + 1, text, ti + run) {
+                        return true;
+                    }
+                    if run == 0 {
+                        break;
+                    }
+                    run -= 1;
+                }
+                floor == 0 && self.match_from(ai + 1, text, ti)
+            }
+        }
+    }
+}
+
+/// How a filter token matches a file name.
+#[derive(Clone, Debug)]
+enum NameMatcher {
+    Substring(String),
+    Glob(String),
+    Regex(RegexPattern),
+}
+
+impl NameMatcher {
+    /// Classify a raw token: `/re/` is an anchored regex, a token with `*`/`?`
+    /// is a glob, anything else is a case-insensitive substring.
+    fn classify(token: &str) -> NameMatcher {
+        if token.len() >= 2 && token.starts_with('/') && token.ends_with('/') {
+            NameMatcher::Regex(RegexPattern::compile(&token[1..token.len() - 1]))
+        } else if token.contains('*') || token.contains('?') {
+            NameMatcher::Glob(token.to_string())
+        } else {
+            NameMatcher::Substring(token.to_lowercase())
+        }
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        match self {
+            NameMatcher::Substring(s) => name.to_lowercase().contains(s),
+            NameMatcher::Glob(p) => glob_match(p, name),
+            NameMatcher::Regex(re) => re.is_match(name),
+        }
+    }
+}
+
+/// A parsed filter query. Whitespace-separated tokens combine: `ext:a,b` /
+/// `-ext:a,b` set include/exclude extension lists; a `!`-prefixed token excludes
+/// names matching it; remaining tokens are name includes (OR-ed). Exclusions
+/// subtract from whatever the includes admit.
+#[derive(Clone, Debug, Default)]
+struct FilterSpec {
+    includes: Vec<NameMatcher>,
+    excludes: Vec<NameMatcher>,
+    include_ext: Vec<String>,
+    exclude_ext: Vec<String>,
+}
+
+impl FilterSpec {
+    fn parse(query: &str) -> FilterSpec {
+        let mut spec = FilterSpec::default();
+        for token in query.split_whitespace() {
+            if let Some(list) = token.strip_prefix("ext:") {
+                spec.include_ext.extend(split_ext_list(list));
+            } else if let Some(list) = token.strip_prefix("-ext:").or_else(|| token.strip_prefix("!ext:")) {
+                spec.exclude_ext.extend(split_ext_list(list));
+            } else if let Some(rest) = token.strip_prefix('!') {
+                if !rest.is_empty() {
+                    spec.excludes.push(NameMatcher::classify(rest));
+                }
+            } else {
+                spec.includes.push(NameMatcher::classify(token));
+            }
+        }
+        spec
+    }
 
-    if ascending { ordering } else { ordering.reverse() }
+    /// Whether the spec admits `item`.
+    fn matches(&self, item: &FileItem) -> bool {
+        let ext = lowercase_extension(&item.name);
+        if !self.include_ext.is_empty() && !self.include_ext.contains(&ext) {
+            return false;
+        }
+        if self.exclude_ext.contains(&ext) {
+            return false;
+        }
+        if self.excludes.iter().any(|m| m.matches(&item.name)) {
+            return false;
+        }
+        if !self.includes.is_empty() && !self.includes.iter().any(|m| m.matches(&item.name)) {
+            return false;
+        }
+        true
+    }
+}
+
+/// Split a comma-separated extension list into lowercase, dot-stripped entries.
+fn split_ext_list(list: &str) -> Vec<String> {
+    list.split(',')
+        .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+        .filter(|e| !e.is_empty())
+        .collect()
 }
 
-/// Filter items by search query
+/// Filter items by search query.
+///
+/// The query is a [`FilterSpec`]: a plain word is a case-insensitive substring
+/// (the original behaviour), but `*`/`?`/`**` globs, `/re/` anchored regexes,
+/// and `ext:`/`-ext:` include/exclude lists are also honoured.
 #[allow(dead_code)] // Used in tests
 fn filter_items(items: &[FileItem], query: &str) -> Vec<FileItem> {
-    if query.is_empty() {
+    if query.trim().is_empty() {
         return items.to_vec();
     }
-    let query_lower = query.to_lowercase();
-    items.iter()
-        .filter(|item| item.name.to_lowercase().contains(&query_lower))
-        .cloned()
-        .collect()
+    let spec = FilterSpec::parse(query);
+    items.iter().filter(|item| spec.matches(item)).cloned().collect()
 }
 
 #[cfg(test)]
@@ -2382,6 +6559,37 @@ mod tests {
         assert_eq!(score, 100.0);
     }
 
+    #[test]
+    fn test_detect_format_signatures() {
+        assert_eq!(detect_format(b"%PDF-1.7"), Some("pdf"));
+        assert_eq!(detect_format(b"PK\x03\x04rest"), Some("zip"));
+        assert_eq!(detect_format(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("jpeg"));
+        assert_eq!(detect_format(&[0x89, b'P', b'N', b'G', 0x0D]), Some("png"));
+        assert_eq!(detect_format(b"GIF89a"), Some("gif"));
+        assert_eq!(detect_format(b"ID3\x03"), Some("mp3"));
+        assert_eq!(detect_format(b"RIFF\x00\x00\x00\x00WAVEfmt"), Some("wav"));
+        assert_eq!(detect_format(&[0x1F, 0x8B, 0x08]), Some("gz"));
+        assert_eq!(detect_format(b"MZ\x90\x00"), Some("exe"));
+        assert_eq!(detect_format(b"plain text"), None);
+        assert_eq!(detect_format(b""), None);
+    }
+
+    #[test]
+    fn test_extensions_for_format_container_family() {
+        // docx/xlsx/pptx share the zip signature and must not false-positive.
+        assert!(extensions_for_format("zip").contains(&"docx"));
+        assert!(extensions_for_format("zip").contains(&"xlsx"));
+        assert!(extensions_for_format("jpeg").contains(&"jpg"));
+        assert!(extensions_for_format("pdf").contains(&"pdf"));
+        assert!(extensions_for_format("pdf").is_empty() == false);
+        assert!(extensions_for_format("nonsense").is_empty());
+    }
+
+    #[test]
+    fn test_verify_extension_no_verdict_without_ext() {
+        assert_eq!(verify_extension("C:\\noext", ""), ExtensionVerdict::NoVerdict);
+    }
+
     #[test]
     fn test_categorize_temp_files_useless() {
         let (cat, score) = categorize_file("C:\\temp\\file.tmp", "file.tmp", false, 100);
@@ -2461,6 +6669,39 @@ mod tests {
         assert_eq!(score, 30.0);
     }
 
+    #[test]
+    fn test_default_ruleset_lookup() {
+        let rs = default_ruleset();
+        assert_eq!(rs.lookup("pdf", 0), Some((FileCategory::Regular, 90.0)));
+        assert_eq!(rs.lookup("png", 0), Some((FileCategory::Regular, 95.0)));
+        // Video size band kicks in at/above 1 GB.
+        assert_eq!(rs.lookup("mp4", 500_000_000), Some((FileCategory::Regular, 85.0)));
+        assert_eq!(rs.lookup("mp4", 2_000_000_000), Some((FileCategory::Regular, 70.0)));
+        // Unknown extension is left to the caller's fallback.
+        assert_eq!(rs.lookup("xyz", 0), None);
+    }
+
+    #[test]
+    fn test_parse_category_names() {
+        assert_eq!(parse_category("Useless"), Some(FileCategory::Useless));
+        assert_eq!(parse_category("must_keep"), Some(FileCategory::MustKeep));
+        assert_eq!(parse_category("bogus"), None);
+    }
+
+    #[test]
+    fn test_parse_ruleset_json_overrides_and_fallback() {
+        // A user rule for heic wins; size bands sort highest-first.
+        let rs = parse_ruleset_json(
+            r#"[{"extensions": [".heic", "avif"], "category": "useless",
+                 "base": 12, "size_bands": [[100, 5], [1000, 2]]}]"#,
+        );
+        assert_eq!(rs.lookup("heic", 0), Some((FileCategory::Useless, 12.0)));
+        assert_eq!(rs.lookup("avif", 1000), Some((FileCategory::Useless, 2.0)));
+        // Malformed JSON degrades to the built-in defaults.
+        let fallback = parse_ruleset_json("not json");
+        assert_eq!(fallback.lookup("pdf", 0), Some((FileCategory::Regular, 90.0)));
+    }
+
     #[test]
     fn test_categorize_iso_low_usefulness() {
         let (cat, score) = categorize_file("C:\\Downloads\\windows.iso", "windows.iso", false, 5_000_000_000);
@@ -2512,6 +6753,23 @@ mod tests {
         assert_eq!(get_file_icon("empty", true, true, FileCategory::Regular), "üìÇ");
     }
 
+    #[test]
+    fn test_theme_icon_names() {
+        assert_eq!(theme_icon_name("jpg", false, FileCategory::Regular), "image-x-generic");
+        assert_eq!(theme_icon_name("mp4", false, FileCategory::Regular), "video-x-generic");
+        assert_eq!(theme_icon_name("rs", false, FileCategory::Regular), "text-x-source");
+        assert_eq!(theme_icon_name("", true, FileCategory::Regular), "folder");
+        // Unknown extension falls back to the category-derived name.
+        assert_eq!(theme_icon_name("xyz", false, FileCategory::Useless), "user-trash");
+    }
+
+    #[test]
+    fn test_is_thumbnailable() {
+        assert!(is_thumbnailable("jpg"));
+        assert!(is_thumbnailable("mp4"));
+        assert!(!is_thumbnailable("txt"));
+    }
+
     #[test]
     fn test_image_icons() {
         assert_eq!(get_file_icon("photo.jpg", false, false, FileCategory::Regular), "üñºÔ∏è");
@@ -2580,6 +6838,9 @@ mod tests {
             usefulness,
             modified: None,
             child_count: None,
+            media_info: None,
+            excluded: false,
+            real_type: None,
         }
     }
 
@@ -2703,6 +6964,64 @@ mod tests {
         assert_eq!(filtered.len(), 0);
     }
 
+    #[test]
+    fn test_filter_glob_pattern() {
+        let items = vec![
+            create_test_item("report.pdf", 100, false, FileCategory::Regular, 50.0),
+            create_test_item("report.txt", 200, false, FileCategory::Regular, 50.0),
+            create_test_item("summary.pdf", 300, false, FileCategory::Regular, 50.0),
+        ];
+
+        let filtered = filter_items(&items, "report.*");
+        assert_eq!(filtered.len(), 2);
+
+        let filtered = filter_items(&items, "*.pdf");
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().all(|i| i.name.ends_with(".pdf")));
+    }
+
+    #[test]
+    fn test_filter_regex_pattern() {
+        let items = vec![
+            create_test_item("img001.png", 100, false, FileCategory::Regular, 50.0),
+            create_test_item("img042.png", 200, false, FileCategory::Regular, 50.0),
+            create_test_item("imgabc.png", 300, false, FileCategory::Regular, 50.0),
+        ];
+
+        let filtered = filter_items(&items, "/img[0-9]+\\.png/");
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().all(|i| i.name.starts_with("img0")));
+    }
+
+    #[test]
+    fn test_filter_extension_include_and_exclude() {
+        let items = vec![
+            create_test_item("a.pdf", 100, false, FileCategory::Regular, 50.0),
+            create_test_item("b.png", 200, false, FileCategory::Regular, 50.0),
+            create_test_item("c.txt", 300, false, FileCategory::Regular, 50.0),
+        ];
+
+        let filtered = filter_items(&items, "ext:pdf,png");
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().all(|i| i.name.ends_with("pdf") || i.name.ends_with("png")));
+
+        let filtered = filter_items(&items, "-ext:txt");
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().all(|i| !i.name.ends_with(".txt")));
+    }
+
+    #[test]
+    fn test_filter_name_exclusion() {
+        let items = vec![
+            create_test_item("keep.txt", 100, false, FileCategory::Regular, 50.0),
+            create_test_item("drop_backup.txt", 200, false, FileCategory::Regular, 50.0),
+        ];
+
+        let filtered = filter_items(&items, "!*backup*");
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].name, "keep.txt");
+    }
+
     // ==================== Multi-Selection Tests ====================
 
     #[test]
@@ -2763,6 +7082,9 @@ mod tests {
             usefulness: 50.0,
             modified: None,
             child_count: Some(0),
+            media_info: None,
+            excluded: false,
+            real_type: None,
         };
         assert!(empty_folder.child_count == Some(0));
 
@@ -2775,6 +7097,9 @@ mod tests {
             usefulness: 50.0,
             modified: None,
             child_count: Some(5),
+            media_info: None,
+            excluded: false,
+            real_type: None,
         };
         assert!(non_empty_folder.child_count != Some(0));
     }
@@ -2798,6 +7123,482 @@ mod tests {
         assert_eq!(history[index], PathBuf::from("C:\\Users\\John"));
     }
 
+    // ==================== Extension Filter Tests ====================
+
+    #[test]
+    fn test_parse_extension_list_normalizes() {
+        let set = parse_extension_list(" .LOG, tmp ,,Cache");
+        assert!(set.contains("log"));
+        assert!(set.contains("tmp"));
+        assert!(set.contains("cache"));
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn test_filter_directories_always_kept() {
+        let allowed = parse_extension_list("mp4");
+        assert!(passes_extension_filter("anyfolder", true, 0, &allowed, &HashSet::new(), 0));
+    }
+
+    #[test]
+    fn test_filter_whitelist() {
+        let allowed = parse_extension_list("mp4,mkv");
+        let empty = HashSet::new();
+        assert!(passes_extension_filter("movie.mp4", false, 100, &allowed, &empty, 0));
+        assert!(!passes_extension_filter("notes.txt", false, 100, &allowed, &empty, 0));
+    }
+
+    #[test]
+    fn test_filter_blacklist_and_min_size() {
+        let empty = HashSet::new();
+        let excluded = parse_extension_list("tmp");
+        assert!(!passes_extension_filter("a.tmp", false, 100, &empty, &excluded, 0));
+        // Below the size threshold
+        assert!(!passes_extension_filter("a.txt", false, 50, &empty, &empty, 100));
+        assert!(passes_extension_filter("a.txt", false, 100, &empty, &empty, 100));
+    }
+
+    #[test]
+    fn test_skip_scan_excluded_path_substring() {
+        let allowed = HashSet::new();
+        let excluded = HashSet::new();
+        let skips = vec!["node_modules".to_string()];
+        assert!(should_skip_scan_entry(
+            Path::new("C:\\proj\\node_modules\\pkg"),
+            "pkg",
+            true,
+            &allowed,
+            &excluded,
+            &skips,
+        ));
+        assert!(!should_skip_scan_entry(
+            Path::new("C:\\proj\\src"),
+            "src",
+            true,
+            &allowed,
+            &excluded,
+            &skips,
+        ));
+    }
+
+    #[test]
+    fn test_skip_scan_extension_rules_keep_dirs() {
+        let allowed = parse_extension_list("mp4");
+        let excluded = parse_extension_list("tmp");
+        let no_paths: Vec<String> = Vec::new();
+        // Directories survive the extension rules.
+        assert!(!should_skip_scan_entry(
+            Path::new("/a/folder"), "folder", true, &allowed, &excluded, &no_paths));
+        // Whitelist miss and blacklist hit both skip files.
+        assert!(should_skip_scan_entry(
+            Path::new("/a/notes.txt"), "notes.txt", false, &allowed, &excluded, &no_paths));
+        assert!(should_skip_scan_entry(
+            Path::new("/a/x.tmp"), "x.tmp", false, &HashSet::new(), &excluded, &no_paths));
+        assert!(!should_skip_scan_entry(
+            Path::new("/a/clip.mp4"), "clip.mp4", false, &allowed, &excluded, &no_paths));
+    }
+
+    // ==================== Exclude Glob Tests ====================
+
+    #[test]
+    fn test_glob_match_basics() {
+        assert!(glob_match("*.iso", "ubuntu.iso"));
+        assert!(!glob_match("*.iso", "ubuntu.img"));
+        assert!(glob_match("$*", "$Recycle.Bin"));
+        assert!(glob_match("cache?", "cache1"));
+        assert!(!glob_match("cache?", "cache12"));
+    }
+
+    #[test]
+    fn test_glob_single_star_stops_at_separator() {
+        // A single '*' does not cross path separators...
+        assert!(!glob_match("*/node_modules", "a/b/node_modules"));
+        assert!(glob_match("*/node_modules", "proj/node_modules"));
+        // ...but '**' spans them.
+        assert!(glob_match("**/node_modules", "a/b/node_modules"));
+    }
+
+    #[test]
+    fn test_glob_case_insensitive() {
+        assert!(glob_match("*.ISO", "ubuntu.iso"));
+    }
+
+    #[test]
+    fn test_path_excluded_full_and_basename() {
+        let pats = vec!["**/node_modules".to_string(), "*.iso".to_string()];
+        assert!(path_excluded(&pats, Path::new("C:/proj/node_modules")));
+        assert!(path_excluded(&pats, Path::new("C:/downloads/ubuntu.iso")));
+        assert!(!path_excluded(&pats, Path::new("C:/proj/src")));
+        // Empty pattern set never excludes.
+        assert!(!path_excluded(&[], Path::new("C:/anything.iso")));
+    }
+
+    // ==================== Media Metadata Tests ====================
+
+    #[test]
+    fn test_parse_frame_rate() {
+        assert_eq!(parse_frame_rate("30/1"), Some(30.0));
+        assert!((parse_frame_rate("30000/1001").unwrap() - 29.97).abs() < 0.01);
+        assert_eq!(parse_frame_rate("0/0"), None);
+        assert_eq!(parse_frame_rate("bogus"), None);
+    }
+
+    #[test]
+    fn test_parse_ffprobe_json_streams() {
+        let json = r#"{
+            "format": {"format_long_name": "QuickTime / MOV", "duration": "75.5"},
+            "streams": [
+                {"codec_type": "video", "codec_long_name": "H.264", "width": 1920,
+                 "height": 1080, "avg_frame_rate": "30/1"},
+                {"codec_type": "audio", "codec_long_name": "AAC", "channels": 2,
+                 "sample_rate": "48000"}
+            ]
+        }"#;
+        let info = parse_ffprobe_json(json).unwrap();
+        assert_eq!(info.format, "QuickTime / MOV");
+        assert!((info.duration - 75.5).abs() < 0.001);
+        assert_eq!(info.width, Some(1920));
+        assert_eq!(info.height, Some(1080));
+        assert_eq!(info.framerate, Some(30.0));
+        assert_eq!(info.channels, Some(2));
+        assert_eq!(info.sample_rate, Some(48000));
+        assert_eq!(info.codecs, vec!["H.264", "AAC"]);
+    }
+
+    #[test]
+    fn test_refine_media_usefulness() {
+        let long_hd = MediaInfo {
+            duration: 45.0 * 60.0,
+            height: Some(1080),
+            ..MediaInfo::default()
+        };
+        assert!(refine_media_usefulness(70.0, &long_hd) > 70.0);
+
+        let short_sd = MediaInfo {
+            duration: 10.0,
+            height: Some(240),
+            ..MediaInfo::default()
+        };
+        assert!(refine_media_usefulness(70.0, &short_sd) < 70.0);
+    }
+
+    #[test]
+    fn test_default_size_worker_threads_at_least_one() {
+        assert!(default_size_worker_threads() >= 1);
+    }
+
+    #[test]
+    fn test_round_up_to_cluster() {
+        assert_eq!(round_up_to_cluster(0, 4096), 0);
+        assert_eq!(round_up_to_cluster(1, 4096), 4096);
+        assert_eq!(round_up_to_cluster(4096, 4096), 4096);
+        assert_eq!(round_up_to_cluster(4097, 4096), 8192);
+        // A zero cluster size leaves the length untouched.
+        assert_eq!(round_up_to_cluster(1234, 0), 1234);
+    }
+
+    // ==================== Type Filter Tests ====================
+
+    #[test]
+    fn test_file_type_group_buckets() {
+        assert_eq!(file_type_group("png"), Some(FileTypeGroup::Images));
+        assert_eq!(file_type_group("mkv"), Some(FileTypeGroup::Video));
+        assert_eq!(file_type_group("flac"), Some(FileTypeGroup::Audio));
+        assert_eq!(file_type_group("pdf"), Some(FileTypeGroup::Documents));
+        assert_eq!(file_type_group("rs"), Some(FileTypeGroup::Code));
+        assert_eq!(file_type_group("zip"), Some(FileTypeGroup::Archives));
+        assert_eq!(file_type_group("exe"), Some(FileTypeGroup::Executables));
+        assert_eq!(file_type_group("xyz"), None);
+    }
+
+    #[test]
+    fn test_passes_type_filter() {
+        let mut active = HashSet::new();
+        // Empty set passes everything.
+        assert!(passes_type_filter("movie.mkv", false, &active));
+        active.insert(FileTypeGroup::Video);
+        assert!(passes_type_filter("movie.mkv", false, &active));
+        assert!(!passes_type_filter("notes.txt", false, &active));
+        // Directories always pass.
+        assert!(passes_type_filter("folder", true, &active));
+        // Unknown extensions fail when a filter is active.
+        assert!(!passes_type_filter("data.xyz", false, &active));
+    }
+
+    // ==================== Duplicate Selection Tests ====================
+
+    #[test]
+    fn test_duplicate_deletion_candidates_keep_one_skip_protected() {
+        let groups = vec![
+            DuplicateGroup {
+                hash: 1,
+                paths: vec![
+                    PathBuf::from("C:\\a\\one.txt"),
+                    PathBuf::from("C:\\b\\one.txt"),
+                    PathBuf::from("C:\\c\\one.txt"),
+                ],
+                size: 100,
+            },
+            DuplicateGroup {
+                hash: 2,
+                paths: vec![
+                    PathBuf::from("C:\\x\\pagefile.sys"),
+                    PathBuf::from("C:\\y\\pagefile.sys"),
+                ],
+                size: 200,
+            },
+        ];
+        let candidates = duplicate_deletion_candidates(&groups);
+        // First group: two of three redundant copies selected.
+        assert!(candidates.contains(&PathBuf::from("C:\\b\\one.txt")));
+        assert!(candidates.contains(&PathBuf::from("C:\\c\\one.txt")));
+        assert!(!candidates.contains(&PathBuf::from("C:\\a\\one.txt")));
+        // Protected copy is never queued for deletion.
+        assert!(!candidates.iter().any(|p| p.to_string_lossy().contains("pagefile.sys")));
+    }
+
+    #[test]
+    fn test_lower_quality_image_copies_keeps_one() {
+        // Non-existent paths read as 0 bytes, so the first is kept on the tie
+        // and every other member is returned as a deletion candidate.
+        let group = vec![
+            PathBuf::from("C:\\nope\\a.jpg"),
+            PathBuf::from("C:\\nope\\b.jpg"),
+            PathBuf::from("C:\\nope\\c.jpg"),
+        ];
+        let copies = lower_quality_image_copies(&group);
+        assert_eq!(copies.len(), 2);
+        assert!(!copies.contains(&PathBuf::from("C:\\nope\\a.jpg")));
+    }
+
+    #[test]
+    fn test_duplicate_stale_copies_keeps_newest_skips_protected() {
+        // All paths lack mtime (nonexistent), so max_by_key keeps the last one;
+        // the protected member is never returned.
+        let groups = vec![DuplicateGroup {
+            hash: 1,
+            paths: vec![
+                PathBuf::from("C:\\a\\dup.txt"),
+                PathBuf::from("C:\\b\\pagefile.sys"),
+                PathBuf::from("C:\\c\\dup.txt"),
+            ],
+            size: 10,
+        }];
+        let stale = duplicate_stale_copies(&groups);
+        assert!(stale.contains(&PathBuf::from("C:\\a\\dup.txt")));
+        assert!(!stale.iter().any(|p| p.to_string_lossy().contains("pagefile.sys")));
+        assert!(!stale.contains(&PathBuf::from("C:\\c\\dup.txt")));
+    }
+
+    #[test]
+    fn test_resolve_duplicates_strategies() {
+        // Nonexistent paths ⇒ mtime None for all, so max_by_key keeps the last
+        // member (newest) and min_by_key the first (oldest).
+        let group = DuplicateGroup {
+            hash: 7,
+            paths: vec![
+                PathBuf::from("C:\\a\\dup.txt"),
+                PathBuf::from("C:\\b\\dup.txt"),
+                PathBuf::from("C:\\c\\dup.txt"),
+            ],
+            size: 10,
+        };
+        let a = PathBuf::from("C:\\a\\dup.txt");
+        let b = PathBuf::from("C:\\b\\dup.txt");
+        let c = PathBuf::from("C:\\c\\dup.txt");
+
+        assert_eq!(resolve_duplicates(&group, DuplicateStrategy::KeepOneNewest), vec![a.clone(), b.clone()]);
+        assert_eq!(resolve_duplicates(&group, DuplicateStrategy::KeepOneOldest), vec![b.clone(), c.clone()]);
+        assert_eq!(resolve_duplicates(&group, DuplicateStrategy::KeepAllExceptNewest), vec![c.clone()]);
+        assert_eq!(resolve_duplicates(&group, DuplicateStrategy::KeepAllExceptOldest), vec![a.clone()]);
+    }
+
+    #[test]
+    fn test_resolve_duplicates_skips_protected() {
+        let group = DuplicateGroup {
+            hash: 1,
+            paths: vec![
+                PathBuf::from("C:\\a\\dup.txt"),
+                PathBuf::from("C:\\Windows\\dup.txt"),
+            ],
+            size: 10,
+        };
+        // KeepOneOldest deletes all but the first; the protected member must
+        // never be offered for deletion.
+        let marked = resolve_duplicates(&group, DuplicateStrategy::KeepOneOldest);
+        assert!(!marked.iter().any(|p| p.to_string_lossy().contains("Windows")));
+    }
+
+    #[test]
+    fn test_total_reclaimable_sums_groups() {
+        let groups = vec![
+            DuplicateGroup { hash: 1, paths: vec![PathBuf::from("a"), PathBuf::from("b")], size: 100 },
+            DuplicateGroup { hash: 2, paths: vec![PathBuf::from("c"), PathBuf::from("d"), PathBuf::from("e")], size: 10 },
+        ];
+        // 1 extra copy * 100 + 2 extra copies * 10 = 120.
+        assert_eq!(total_reclaimable(&groups), 120);
+    }
+
+    // ==================== Acoustic Fingerprint Tests ====================
+
+    #[test]
+    fn test_quantize_chroma_thresholds_on_mean() {
+        // Bins 0 and 6 are above the mean, the rest are zero.
+        let mut chroma = [0.0f32; 12];
+        chroma[0] = 10.0;
+        chroma[6] = 8.0;
+        let d = quantize_chroma(&chroma);
+        assert_eq!(d & 1, 1);
+        assert_eq!((d >> 6) & 1, 1);
+        assert_eq!((d >> 1) & 1, 0);
+    }
+
+    #[test]
+    fn test_compare_fingerprints_identical_and_disjoint() {
+        let a = vec![0b0000_0000_0001u16, 0b0000_0000_0010, 0b0000_0000_0100];
+        assert_eq!(compare_fingerprints(&a, &a), 0.0);
+        // Every bit differs → the maximal normalised difference, 1.0.
+        let b = vec![!a[0] & 0x0FFF, !a[1] & 0x0FFF, !a[2] & 0x0FFF];
+        assert!(compare_fingerprints(&a, &b) > compare_fingerprints(&a, &a));
+    }
+
+    #[test]
+    fn test_compare_fingerprints_best_offset_alignment() {
+        // `short` is a contiguous slice of `long`, just shifted; the best
+        // alignment should find a perfect (zero) match.
+        let long = vec![1u16, 2, 3, 4, 5, 6];
+        let short = vec![3u16, 4, 5];
+        assert_eq!(compare_fingerprints(&short, &long), 0.0);
+    }
+
+    #[test]
+    fn test_cluster_fingerprints_groups_close_pairs() {
+        let prints = vec![
+            (PathBuf::from("a.mp3"), vec![1u16, 2, 3]),
+            (PathBuf::from("b.flac"), vec![1u16, 2, 3]),
+            (PathBuf::from("c.mp3"), vec![0xFFFu16, 0xFFF, 0xFFF]),
+        ];
+        let clusters = cluster_fingerprints(&prints, 0.05);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].len(), 2);
+    }
+
+    #[test]
+    fn test_resample_nearest_halves_length() {
+        let samples: Vec<f32> = (0..100).map(|i| i as f32).collect();
+        let (out, rate) = resample_nearest(&samples, 22050, 11025);
+        assert_eq!(rate, 11025);
+        assert_eq!(out.len(), 50);
+    }
+
+    // ==================== Perceptual Hash Tests ====================
+
+    #[test]
+    fn test_hamming_distance() {
+        assert_eq!(hamming_distance(0, 0), 0);
+        assert_eq!(hamming_distance(0b1010, 0b0000), 2);
+        assert_eq!(hamming_distance(u64::MAX, 0), 64);
+    }
+
+    #[test]
+    fn test_compute_dhash_gradient() {
+        // A left-to-right brightening gradient: left is never brighter than
+        // right, so every difference bit is 0.
+        let mut grid = [0u8; 9 * 8];
+        for row in 0..8 {
+            for col in 0..9 {
+                grid[row * 9 + col] = (col * 28) as u8;
+            }
+        }
+        assert_eq!(compute_dhash(&grid), 0);
+    }
+
+    #[test]
+    fn test_cluster_by_hamming_groups_close() {
+        let hashes = vec![
+            (PathBuf::from("a"), 0b0000u64),
+            (PathBuf::from("b"), 0b0001u64), // distance 1 from a
+            (PathBuf::from("c"), 0xFFFF_FFFFu64), // far away -> singleton, dropped
+        ];
+        let clusters = cluster_by_hamming(&hashes, 4);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].len(), 2);
+    }
+
+    #[test]
+    fn test_hamming_similarity_percent() {
+        assert_eq!(hamming_similarity_percent(0), 100.0);
+        assert_eq!(hamming_similarity_percent(64), 0.0);
+        assert_eq!(hamming_similarity_percent(32), 50.0);
+    }
+
+    #[test]
+    fn test_group_similarity_empty_group_is_exact() {
+        // A group with nothing to decode has no disagreeing pair, so 100%.
+        assert_eq!(group_similarity_percent(&[]), 100.0);
+    }
+
+    // ==================== Filesystem Metadata Tests ====================
+
+    #[test]
+    fn test_network_fs_detection() {
+        assert!(is_network_fs("nfs"));
+        assert!(is_network_fs("NFS4"));
+        assert!(is_network_fs("cifs"));
+        assert!(is_network_fs("fuse.sshfs"));
+    }
+
+    #[test]
+    fn test_local_fs_not_network() {
+        assert!(!is_network_fs("ext4"));
+        assert!(!is_network_fs("ntfs"));
+        assert!(!is_network_fs("btrfs"));
+        assert!(!is_network_fs("apfs"));
+    }
+
+    // ==================== Duplicate Finder Tests ====================
+
+    #[test]
+    fn test_reclaimable_bytes_single_copy() {
+        // A lone file wastes nothing.
+        assert_eq!(reclaimable_bytes(1000, 1), 0);
+        assert_eq!(reclaimable_bytes(1000, 0), 0);
+    }
+
+    #[test]
+    fn test_reclaimable_bytes_multiple_copies() {
+        // Every copy beyond the first is reclaimable.
+        assert_eq!(reclaimable_bytes(1000, 2), 1000);
+        assert_eq!(reclaimable_bytes(1000, 4), 3000);
+    }
+
+    #[test]
+    fn test_group_files_by_size_drops_unique() {
+        let files = vec![
+            (PathBuf::from("a"), 100),
+            (PathBuf::from("b"), 100),
+            (PathBuf::from("c"), 200), // unique size -> dropped
+            (PathBuf::from("d"), 300),
+            (PathBuf::from("e"), 300),
+            (PathBuf::from("f"), 300),
+        ];
+        let mut groups = group_files_by_size(&files);
+        groups.sort_by_key(|g| g.len());
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].len(), 2);
+        assert_eq!(groups[1].len(), 3);
+    }
+
+    #[test]
+    fn test_prune_singleton_groups() {
+        let mut map: HashMap<u64, Vec<u32>> = HashMap::new();
+        map.insert(1, vec![10]);
+        map.insert(2, vec![20, 21]);
+        let groups = prune_singleton_groups(map);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].len(), 2);
+    }
+
     // ==================== Edge Case Tests ====================
 
     #[test]
@@ -2831,4 +7632,160 @@ mod tests {
         let (cat, _) = categorize_file("C:\\file [backup].txt", "file [backup].txt", false, 100);
         assert!(matches!(cat, FileCategory::Regular));
     }
+
+    // ==================== Natural Sort Tests ====================
+
+    #[test]
+    fn test_natural_cmp_numeric_runs() {
+        assert_eq!(natural_cmp("file2", "file10"), std::cmp::Ordering::Less);
+        assert_eq!(natural_cmp("file10", "file2"), std::cmp::Ordering::Greater);
+        assert_eq!(natural_cmp("img1", "img1"), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn test_natural_cmp_orders_sequence() {
+        let mut names = vec!["file10", "file1", "file2", "file20"];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(names, vec!["file1", "file2", "file10", "file20"]);
+    }
+
+    #[test]
+    fn test_natural_cmp_case_insensitive_text() {
+        assert_eq!(natural_cmp("Photo3", "photo3"), std::cmp::Ordering::Equal);
+        assert_eq!(natural_cmp("apple", "Banana"), std::cmp::Ordering::Less);
+    }
+
+    #[test]
+    fn test_natural_cmp_leading_zeros() {
+        // Equal numeric value: the shorter (less-padded) run sorts first.
+        assert_eq!(natural_cmp("v1", "v01"), std::cmp::Ordering::Less);
+        // Numeric value wins regardless of zero padding.
+        assert_eq!(natural_cmp("v009", "v10"), std::cmp::Ordering::Less);
+    }
+
+    #[test]
+    fn test_natural_cmp_prefix() {
+        assert_eq!(natural_cmp("file", "file1"), std::cmp::Ordering::Less);
+    }
+
+    /// Build a [`FileItem`] for an on-disk regular file, reading its length.
+    fn file_item(path: &Path) -> FileItem {
+        let size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        let name = path.file_name().unwrap().to_string_lossy().to_string();
+        let (category, usefulness) = categorize_file(&path.to_string_lossy(), &name, false, size);
+        FileItem {
+            path: path.to_path_buf(),
+            name,
+            size,
+            is_dir: false,
+            category,
+            usefulness,
+            modified: None,
+            child_count: None,
+            media_info: None,
+            excluded: false,
+            real_type: None,
+        }
+    }
+
+    #[test]
+    fn test_find_duplicates_groups_identical_content() {
+        let root = std::env::temp_dir().join("dcd_dups");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("one.bin"), b"identical payload").unwrap();
+        fs::write(root.join("two.bin"), b"identical payload").unwrap();
+        fs::write(root.join("unique.bin"), b"something else entirely").unwrap();
+        let items: Vec<FileItem> = ["one.bin", "two.bin", "unique.bin"]
+            .iter()
+            .map(|n| file_item(&root.join(n)))
+            .collect();
+        let groups = find_duplicates(&items);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].len(), 2);
+        // All-but-one is offered for deletion.
+        assert_eq!(duplicate_extra_copies(&groups).len(), 1);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_progress_fraction() {
+        let p = ProgressData {
+            current_stage: 2,
+            max_stage: 3,
+            entries_checked: 3,
+            entries_to_check: 4,
+            tool_type: "duplicates",
+        };
+        assert_eq!(p.fraction(), 0.75);
+        // Guard against division by zero on an unsized stage.
+        let p0 = ProgressData { entries_to_check: 0, ..p };
+        assert_eq!(p0.fraction(), 0.0);
+    }
+
+    #[test]
+    fn test_scan_duplicates_reports_progress() {
+        let root = std::env::temp_dir().join("dcd_progress");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("one.bin"), b"identical payload").unwrap();
+        fs::write(root.join("two.bin"), b"identical payload").unwrap();
+
+        let (tx, rx) = channel();
+        let (ptx, prx) = channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        scan_duplicates_reporting(&root, cancel, tx, Some(ptx));
+
+        let updates: Vec<ProgressData> = prx.try_iter().collect();
+        assert!(!updates.is_empty());
+        assert!(updates.iter().all(|u| u.tool_type == "duplicates"));
+        assert!(updates.iter().all(|u| u.current_stage >= 1 && u.current_stage <= u.max_stage));
+        // The finder still reported the duplicate pair.
+        assert_eq!(rx.try_iter().count(), 1);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_categorize_empty_file() {
+        let (cat, score) = categorize_file("C:\\Users\\John\\empty.dat", "empty.dat", false, 0);
+        assert_eq!(cat, FileCategory::Empty);
+        assert_eq!(score, DEGENERATE_USEFULNESS);
+    }
+
+    #[test]
+    fn test_empty_folder_is_degenerate() {
+        assert!(FileCategory::EmptyFolder.is_degenerate());
+        assert!(FileCategory::Empty.is_degenerate());
+        assert!(FileCategory::BrokenSymlink.is_degenerate());
+        assert!(!FileCategory::Regular.is_degenerate());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_broken_symlink_detected() {
+        use std::os::unix::fs::symlink;
+        let root = std::env::temp_dir().join("dcd_symlink");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let link = root.join("dangling");
+        symlink(root.join("does_not_exist"), &link).unwrap();
+        let (cat, score) = categorize_file(&link.to_string_lossy(), "dangling", false, 0);
+        assert_eq!(cat, FileCategory::BrokenSymlink);
+        assert_eq!(score, DEGENERATE_USEFULNESS);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_ignore_set_parse_and_match() {
+        let set = IgnoreSet::parse("# comment\n\n*.log\nbuild/\n!keep.log\n");
+        assert!(set.is_ignored("app.log", false));
+        assert!(set.is_ignored("build", true));
+        // Directory-only rule does not match a file of the same name.
+        assert!(!set.is_ignored("build", false));
+        // Later negation re-includes a path an earlier rule excluded.
+        assert!(!set.is_ignored("keep.log", false));
+        // Unmatched paths are kept.
+        assert!(!set.is_ignored("main.rs", false));
+    }
+
 }